@@ -4,7 +4,15 @@
 #![macro_use]
 pub(crate) mod fmt;
 
-use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay, SequenceStep};
+use bm13xx_asic::{
+    check_addr_interval, check_version_mask, chip_address,
+    core_register::*,
+    max_clki_baudrate,
+    nonce::{NonceField, NonceLayout},
+    pll::{pll_frequency, pll_roles, PllRole},
+    register::*,
+    Asic, ChipSnapshot, CmdDelay, SequenceStep,
+};
 use bm13xx_protocol::command::{Command, Destination};
 
 use fugit::HertzU64;
@@ -12,6 +20,21 @@ use heapless::FnvIndexMap;
 
 pub const BM1366_CHIP_ID: u16 = 0x1366;
 pub const BM1366_CORE_CNT: usize = 112;
+/// Number of enabled Small Cores.
+///
+/// The Core layout gives `BM1366_CORE_CNT * BM1366_CORE_SMALL_CORE_CNT = 896` Small Cores, but only
+/// 894 of them are hashing (2 are disabled),
+/// matching the count used by other mining firmwares.
+/// This is the count used for the theoretical Hashrate.
+///
+/// ### Example
+///
+/// ```
+/// use bm1366::{BM1366_CORE_CNT, BM1366_CORE_SMALL_CORE_CNT, BM1366_SMALL_CORE_CNT};
+///
+/// assert_eq!(BM1366_CORE_CNT * BM1366_CORE_SMALL_CORE_CNT, 896);
+/// assert_eq!(BM1366_CORE_CNT * BM1366_CORE_SMALL_CORE_CNT - BM1366_SMALL_CORE_CNT, 2);
+/// ```
 pub const BM1366_SMALL_CORE_CNT: usize = 894;
 pub const BM1366_CORE_SMALL_CORE_CNT: usize = 8;
 pub const BM1366_DOMAIN_CNT: usize = 1;
@@ -20,9 +43,55 @@ pub const BM1366_PLL_ID_HASH: usize = 0; // PLL0 is used for Hashing
 pub const BM1366_PLL_OUT_HASH: usize = 0; // specifically PLL0_OUT0 is used for Hashing
 pub const BM1366_PLL_ID_UART: usize = 1; // PLL1 can be used for UART Baudrate
 pub const BM1366_PLL_OUT_UART: usize = 4; // specifically PLL1_OUT4 can be used for UART Baudrate
+/// Role of each PLL.
+///
+/// ### Example
+/// ```
+/// use bm1366::{BM1366_PLL_ID_HASH, BM1366_PLL_ID_UART, BM1366_PLL_ROLES};
+/// use bm13xx_asic::pll::PllRole;
+///
+/// assert_eq!(BM1366_PLL_ROLES, [PllRole::Hash, PllRole::Uart]);
+/// assert_eq!(BM1366_PLL_ROLES[BM1366_PLL_ID_HASH], PllRole::Hash);
+/// assert_eq!(BM1366_PLL_ROLES[BM1366_PLL_ID_UART], PllRole::Uart);
+/// ```
+pub const BM1366_PLL_ROLES: [PllRole; BM1366_PLL_CNT] =
+    pll_roles(BM1366_PLL_ID_HASH, BM1366_PLL_ID_UART);
+/// Hash frequency above which the ramp steps need a longer delay to settle.
+pub const BM1366_HASH_FREQ_LONG_DELAY: HertzU64 = HertzU64::MHz(380);
+
+// Enabled Small Cores can not exceed the Core layout
+const _: () = core::assert!(BM1366_SMALL_CORE_CNT <= BM1366_CORE_CNT * BM1366_CORE_SMALL_CORE_CNT);
+
+// Core ID, Small Core ID and Chip Address must fit in the Nonce
+const _: () = core::assert!(bm13xx_asic::nonce::nonce_chip_addr_shift(
+    BM1366_CORE_CNT.ilog2() + 1,
+    BM1366_CORE_SMALL_CORE_CNT.ilog2() + 1
+)
+.is_some());
+
+/// Capacity of the registers map.
+pub const BM1366_REGISTER_CAPACITY: usize = 64;
+/// Number of registers set by `reset()`.
+///
+/// The remaining `BM1366_REGISTER_CAPACITY - BM1366_DEFAULT_REGISTER_CNT` slots are free for
+/// user-added registers.
+///
+/// ### Example
+/// ```
+/// use bm1366::{BM1366, BM1366_DEFAULT_REGISTER_CNT, BM1366_REGISTER_CAPACITY};
+///
+/// let bm1366 = BM1366::default();
+/// assert_eq!(bm1366.registers.len(), BM1366_DEFAULT_REGISTER_CNT);
+/// assert_eq!(bm1366.registers.capacity(), BM1366_REGISTER_CAPACITY);
+/// assert_eq!(BM1366_REGISTER_CAPACITY - BM1366_DEFAULT_REGISTER_CNT, 5); // headroom
+/// ```
+pub const BM1366_DEFAULT_REGISTER_CNT: usize = 59;
+
+// The default registers must fit in the registers map
+const _: () = core::assert!(BM1366_DEFAULT_REGISTER_CNT < BM1366_REGISTER_CAPACITY);
 
 /// # BM1366
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 // #[cfg_attr(feature = "defmt-03", derive(defmt::Format))] // FnvIndexMap doesn't implement defmt
 pub struct BM1366 {
     seq_step: SequenceStep,
@@ -35,7 +104,7 @@ pub struct BM1366 {
     pub input_clock_freq: HertzU64,
     pub plls: [bm13xx_asic::pll::Pll; BM1366_PLL_CNT],
     pub chip_addr: u8,
-    pub registers: FnvIndexMap<u8, u32, 64>,
+    pub registers: FnvIndexMap<u8, u32, BM1366_REGISTER_CAPACITY>,
     pub core_registers: FnvIndexMap<u8, u8, 16>,
 }
 
@@ -61,14 +130,167 @@ impl BM1366 {
         self.chip_addr = chip_addr;
     }
 
-    pub fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
-        self.plls[BM1366_PLL_ID_HASH].set_frequency(
-            self.input_clock_freq,
-            BM1366_PLL_OUT_HASH,
-            freq,
-            false,
-        );
-        self
+    /// ## Get the programmed Version Rolling mask
+    ///
+    /// Return `None` if Hardware Version Rolling is disabled.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1366::BM1366;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let mut bm1366 = BM1366::default();
+    /// assert_eq!(bm1366.version_mask(), None);
+    /// while bm1366.set_version_rolling_next(0x1fff_e000).is_some() {}
+    /// assert_eq!(bm1366.version_mask(), Some(0x1fff_e000));
+    /// ```
+    pub fn version_mask(&self) -> Option<u32> {
+        let vers_roll = VersionRolling(*self.registers.get(&VersionRolling::ADDR).unwrap_or(&0));
+        vers_roll.enabled().then_some(vers_roll.mask())
+    }
+
+    /// ## Check if a Hash Frequency change needs a Core reset
+    ///
+    /// Heuristic based on the `set_hash_freq_next()` ramp: crossing `BM1366_HASH_FREQ_LONG_DELAY`
+    /// changes the settling behavior of the chip, so the Cores are better reset between the
+    /// frequency change and the next jobs.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1366::BM1366;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1366 = BM1366::default();
+    /// bm1366.set_hash_freq(HertzU64::MHz(200));
+    /// assert!(!bm1366.freq_change_needs_reset(HertzU64::MHz(250))); // small change
+    /// assert!(bm1366.freq_change_needs_reset(HertzU64::MHz(500))); // large change, crossing 380 MHz
+    /// bm1366.set_hash_freq(HertzU64::MHz(450));
+    /// assert!(!bm1366.freq_change_needs_reset(HertzU64::MHz(500)));
+    /// assert!(bm1366.freq_change_needs_reset(HertzU64::MHz(300)));
+    /// ```
+    pub fn freq_change_needs_reset(&self, new_freq: HertzU64) -> bool {
+        (self.hash_freq() > BM1366_HASH_FREQ_LONG_DELAY) != (new_freq > BM1366_HASH_FREQ_LONG_DELAY)
+    }
+
+    /// ## Set the Hash PLL dividers
+    ///
+    /// Bypass the frequency solver and program the Hash PLL with the given dividers, keeping
+    /// the current output divider. The dividers are checked, see `PllDividers::check()`.
+    /// Returns the PLL0Parameter write command.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1366::BM1366;
+    /// use bm13xx_asic::{pll::PllDividers, Asic, CmdDelay, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1366 = BM1366::default();
+    /// let cmd = bm1366.set_hash_pll_dividers(192, 2, 5, 1).unwrap(); // VCO at 2400 MHz, high band
+    /// assert_eq!(cmd, CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xd0, 0xc0, 0x02, 0x51, 0x13], delay_ms: 400});
+    /// assert_eq!(bm1366.hash_freq(), HertzU64::MHz(200));
+    /// assert_eq!(
+    ///     bm1366.set_hash_pll_dividers(192, 2, 1, 5),
+    ///     Err(Error::InvalidPllDividers {
+    ///         dividers: PllDividers { fb_div: 192, ref_div: 2, post1_div: 1, post2_div: 5, out_div: 0 }
+    ///     })
+    /// );
+    /// assert_eq!(bm1366.hash_freq(), HertzU64::MHz(200)); // unchanged
+    /// ```
+    pub fn set_hash_pll_dividers(
+        &mut self,
+        fb_div: u16,
+        ref_div: u8,
+        post1_div: u8,
+        post2_div: u8,
+    ) -> bm13xx_asic::Result<CmdDelay> {
+        let pll = &mut self.plls[BM1366_PLL_ID_HASH];
+        let dividers = bm13xx_asic::pll::PllDividers {
+            fb_div,
+            ref_div,
+            post1_div,
+            post2_div,
+            out_div: pll.out_div(BM1366_PLL_OUT_HASH),
+        };
+        dividers.check(self.input_clock_freq)?;
+        pll.set_dividers(BM1366_PLL_OUT_HASH, &dividers).enable();
+        if pll.vco_freq(self.input_clock_freq) >= HertzU64::MHz(2400) {
+            pll.set_vco_high_freq();
+        } else {
+            pll.set_vco_low_freq();
+        }
+        self.registers
+            .insert(PLL0Parameter::ADDR, pll.parameter())
+            .unwrap();
+        Ok(CmdDelay {
+            cmd: Command::write_reg(PLL0Parameter::ADDR, pll.parameter(), Destination::All),
+            delay_ms: 400,
+        })
+    }
+
+    /// ## Get the Hash PLL post dividers split
+    ///
+    /// Decode the `(POSTDIV1, POSTDIV2)` fields from the PLL0Parameter register, as chosen by the
+    /// frequency solver or programmed with `set_hash_pll_dividers()`.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1366::BM1366;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1366 = BM1366::default();
+    /// assert_eq!(bm1366.hash_pll_postdiv_split(), (6, 5)); // PLL0Parameter reset value
+    /// while bm1366.set_hash_freq_next(HertzU64::MHz(75)).is_some() {}
+    /// assert_eq!(bm1366.hash_pll_postdiv_split(), (6, 3)); // 0xc0a8_0263
+    /// bm1366.set_hash_pll_dividers(192, 2, 5, 1).unwrap();
+    /// assert_eq!(bm1366.hash_pll_postdiv_split(), (5, 1));
+    /// ```
+    pub fn hash_pll_postdiv_split(&self) -> (u8, u8) {
+        let mut pll = bm13xx_asic::pll::Pll::default();
+        pll.set_parameter(*self.registers.get(&PLL0Parameter::ADDR).unwrap_or(&0));
+        (pll.post1_div(), pll.post2_div())
+    }
+
+    /// ## Check the Version Rolling register against the expected mask
+    ///
+    /// `version_mask` is `None` if Version Rolling is expected to be disabled.
+    ///
+    /// ### Errors
+    ///
+    /// - Missing register if the VersionRolling register is not in the map
+    /// - Version rolling mismatch if the register does not agree with `version_mask`
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1366::BM1366;
+    /// use bm13xx_asic::{register::*, Asic, Error};
+    ///
+    /// let mut bm1366 = BM1366::default();
+    /// assert_eq!(bm1366.check_version_rolling(None), Ok(()));
+    /// while bm1366.set_version_rolling_next(0x1fff_e000).is_some() {}
+    /// assert_eq!(bm1366.check_version_rolling(Some(0x1fff_e000)), Ok(()));
+    /// // desync the register from the expected state
+    /// bm1366.registers.insert(VersionRolling::ADDR, 0x0000_ffff).unwrap();
+    /// assert_eq!(
+    ///     bm1366.check_version_rolling(Some(0x1fff_e000)),
+    ///     Err(Error::VersionRollingMismatch { expected: Some(0x1fff_e000), programmed: None })
+    /// );
+    /// ```
+    pub fn check_version_rolling(&self, version_mask: Option<u32>) -> bm13xx_asic::Result<()> {
+        let vers_roll = VersionRolling(*self.registers.get(&VersionRolling::ADDR).ok_or(
+            bm13xx_asic::Error::MissingRegister {
+                reg_addr: VersionRolling::ADDR,
+            },
+        )?);
+        let programmed = vers_roll.enabled().then_some(vers_roll.mask());
+        if programmed != version_mask {
+            return Err(bm13xx_asic::Error::VersionRollingMismatch {
+                expected: version_mask,
+                programmed,
+            });
+        }
+        Ok(())
     }
 
     /*
@@ -179,7 +401,7 @@ impl Default for BM1366 {
             input_clock_freq: HertzU64::MHz(25),
             plls: [bm13xx_asic::pll::Pll::default(); BM1366_PLL_CNT],
             chip_addr: 0,
-            registers: FnvIndexMap::<_, _, 64>::new(),
+            registers: FnvIndexMap::<_, _, BM1366_REGISTER_CAPACITY>::new(),
             core_registers: FnvIndexMap::<_, _, 16>::new(),
         };
         bm1366.reset();
@@ -187,16 +409,43 @@ impl Default for BM1366 {
     }
 }
 
+/// ## Take a snapshot of a BM1366
+///
+/// ### Example
+/// ```
+/// use bm1366::BM1366;
+/// use bm13xx_asic::{register::*, Asic, ChipSnapshot};
+/// use fugit::HertzU64;
+///
+/// let mut bm1366 = BM1366::default();
+/// bm1366.set_chip_addr(4);
+/// bm1366.set_hash_freq(HertzU64::MHz(400));
+/// let snap = ChipSnapshot::from(&bm1366);
+/// assert_eq!(snap.chip_id, 0x1366);
+/// assert_eq!(snap.chip_addr, 4);
+/// assert_eq!(snap.hash_freq_hz, 400_000_000);
+/// assert_eq!(snap.version_rolling, None);
+/// bm1366.registers.insert(VersionRolling::ADDR, 0x9000_ffff).unwrap();
+/// assert_eq!(ChipSnapshot::from(&bm1366).version_rolling, Some(0x1fffe000));
+/// ```
+impl From<&BM1366> for ChipSnapshot {
+    fn from(bm: &BM1366) -> Self {
+        ChipSnapshot::from_asic(bm, bm.chip_addr, bm.version_mask())
+    }
+}
+
 impl Asic for BM1366 {
     /// ## Reset the Chip to default state
+    ///
+    /// The `input_clock_freq` is kept: it is the frequency of the oscillator driving CLKI, which the
+    /// PLL solver keeps needing after the reset.
     fn reset(&mut self) {
         self.seq_step = SequenceStep::default();
         self.sha = bm13xx_asic::sha::Sha::default();
-        self.input_clock_freq = HertzU64::MHz(25);
         self.plls = [bm13xx_asic::pll::Pll::default(); BM1366_PLL_CNT];
         self.chip_addr = 0;
-        self.registers = FnvIndexMap::<_, _, 64>::new();
-        self.core_registers = FnvIndexMap::<_, _, 16>::new();
+        self.registers.clear();
+        self.core_registers.clear();
 
         // Default PLLs Parameter
         self.plls[0].set_parameter(0xC054_0165);
@@ -422,7 +671,22 @@ impl Asic for BM1366 {
     /// assert_eq!(bm1366.set_hash_freq(HertzU64::MHz(200)).hash_freq(), HertzU64::MHz(200));
     /// ```
     fn hash_freq(&self) -> HertzU64 {
-        self.plls[BM1366_PLL_ID_HASH].frequency(self.input_clock_freq, BM1366_PLL_OUT_HASH)
+        pll_frequency(
+            &self.plls,
+            self.input_clock_freq,
+            BM1366_PLL_ID_HASH,
+            BM1366_PLL_OUT_HASH,
+        )
+    }
+
+    fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
+        self.plls[BM1366_PLL_ID_HASH].set_frequency(
+            self.input_clock_freq,
+            BM1366_PLL_OUT_HASH,
+            freq,
+            false,
+        );
+        self
     }
 
     /// ## Init the Chip command list
@@ -582,6 +846,13 @@ impl Asic for BM1366 {
     /// assert_eq!(bm1366.registers.get(&IoDriverStrenghtConfiguration::ADDR).unwrap(), &0x0211_1111);
     /// assert_eq!(bm1366.registers.get(&PLL1Parameter::ADDR).unwrap(), &0xC070_0111);
     /// assert_eq!(bm1366.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(), &0x1560_0700);
+    /// // Common 115200 baud, from CLKI
+    /// let mut last = None;
+    /// while let Some(step) = bm1366.set_baudrate_next(115_200, 1, 10, 2) {
+    ///     last = Some(step);
+    /// }
+    /// assert_eq!(last, Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x28, 0x11, 0x60, 0x1a, 0x00, 0x19], delay_ms: 200}));
+    /// assert_eq!(FastUARTConfigurationV2(*bm1366.registers.get(&FastUARTConfigurationV2::ADDR).unwrap()).bt8d(), 26);
     /// ```
     fn set_baudrate_next(
         &mut self,
@@ -617,9 +888,10 @@ impl Asic for BM1366 {
                         cmd: Command::write_reg(
                             IoDriverStrenghtConfiguration::ADDR,
                             io_drv_st_cfg,
-                            Destination::Chip(
-                                (((dom + 1) * domain_asic_cnt - 1) * asic_addr_interval) as u8,
-                            ),
+                            Destination::Chip(chip_address(
+                                (dom + 1) * domain_asic_cnt - 1,
+                                asic_addr_interval,
+                            )?),
                         ),
                         delay_ms: 0,
                     })
@@ -644,7 +916,10 @@ impl Asic for BM1366 {
                         cmd: Command::write_reg(
                             UARTRelay::ADDR,
                             uart_delay,
-                            Destination::Chip((dom * domain_asic_cnt * asic_addr_interval) as u8),
+                            Destination::Chip(chip_address(
+                                dom * domain_asic_cnt,
+                                asic_addr_interval,
+                            )?),
                         ),
                         delay_ms: 0,
                     })
@@ -670,14 +945,15 @@ impl Asic for BM1366 {
                         cmd: Command::write_reg(
                             UARTRelay::ADDR,
                             uart_delay,
-                            Destination::Chip(
-                                (((dom + 1) * domain_asic_cnt - 1) * asic_addr_interval) as u8,
-                            ),
+                            Destination::Chip(chip_address(
+                                (dom + 1) * domain_asic_cnt - 1,
+                                asic_addr_interval,
+                            )?),
                         ),
                         delay_ms: if step == sub_seq4_start - 1 { 130 } else { 0 },
                     })
                 } else if step == sub_seq4_start {
-                    if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+                    if baudrate <= max_clki_baudrate(self.input_clock_freq) {
                         self.seq_step = SequenceStep::Baudrate(end);
                         let fbase = self.input_clock_freq.raw() as u32;
                         let bt8d = (fbase / (8 * baudrate)) - 1;
@@ -726,7 +1002,7 @@ impl Asic for BM1366 {
                     }
                 } else if step == sub_seq5_start {
                     self.seq_step = SequenceStep::Baudrate(end);
-                    if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+                    if baudrate <= max_clki_baudrate(self.input_clock_freq) {
                         // should not be reached for 2 reasons:
                         // - in step above we jump directly to end
                         // - after setting the chip's FastUartConfiguration with bclk_sel(BaudrateClockSelectV2::Clki) in previous step
@@ -746,9 +1022,13 @@ impl Asic for BM1366 {
                             delay_ms: 0,
                         })
                     } else {
-                        let fbase = self.plls[BM1366_PLL_ID_UART]
-                            .frequency(self.input_clock_freq, BM1366_PLL_OUT_UART)
-                            .raw();
+                        let fbase = pll_frequency(
+                            &self.plls,
+                            self.input_clock_freq,
+                            BM1366_PLL_ID_UART,
+                            BM1366_PLL_OUT_UART,
+                        )
+                        .raw();
                         let bt8d = (fbase as u32 / (2 * baudrate)) - 1;
                         let fast_uart_cfg = FastUARTConfigurationV2(
                             *self.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(),
@@ -779,6 +1059,8 @@ impl Asic for BM1366 {
                 }
             }
             _ => {
+                // do not start a sequence addressing chips out of the 8 bits address space
+                check_addr_interval(chain_domain_cnt * domain_asic_cnt, asic_addr_interval).ok()?;
                 // authorize a SetBaudrate sequence start whatever the current step was
                 self.seq_step = SequenceStep::Baudrate(sub_seq1_start);
                 let io_drv_st_cfg = IoDriverStrenghtConfiguration(
@@ -1041,8 +1323,8 @@ impl Asic for BM1366 {
                     // authorize a ResetCore sequence start whatever the current step was
                     self.seq_step = SequenceStep::ResetCore(0);
                     let reg_a8 = RegA8(*self.registers.get(&RegA8::ADDR).unwrap())
-                        .set_b8()
-                        .set_b7_4(0xf)
+                        .enable_core_reset()
+                        .set_core_reset_mask(0xf)
                         .val();
                     self.registers.insert(RegA8::ADDR, reg_a8).unwrap();
                     Some(CmdDelay {
@@ -1058,8 +1340,8 @@ impl Asic for BM1366 {
     ///
     /// ### Example
     /// ```
-    /// use bm1366::{BM1366, BM1366_PLL_ID_HASH};
-    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use bm1366::{BM1366, BM1366_PLL_ID_HASH, BM1366_PLL_OUT_HASH};
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay, Error};
     /// use fugit::HertzU64;
     ///
     /// let mut bm1366 = BM1366::default();
@@ -1072,6 +1354,20 @@ impl Asic for BM1366 {
     /// assert_eq!(bm1366.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xa8, 0x02, 0x63, 0x14], delay_ms: 400}));
     /// assert_eq!(bm1366.set_hash_freq_next(HertzU64::MHz(75)), None);
     /// assert_eq!(bm1366.plls[BM1366_PLL_ID_HASH].parameter(), 0xc0a8_0263);
+    /// // Ramp profile, steps above 380 MHz need a longer delay
+    /// let profile = BM1366::default().hash_freq_profile::<64>(HertzU64::MHz(400)).unwrap();
+    /// assert_eq!(profile.len(), 57); // 1 divider step, then 6.25 MHz steps from 50 MHz
+    /// assert_eq!(profile[0], (HertzU64::MHz(50), 2));
+    /// assert_eq!(profile[profile.len() - 1], (HertzU64::MHz(400), 2300));
+    /// assert!(profile[1..].iter().all(|&(freq, delay_ms)| delay_ms == if freq > HertzU64::MHz(380) { 2300 } else { 400 }));
+    /// assert_eq!(BM1366::default().hash_freq_profile::<8>(HertzU64::MHz(400)), Err(Error::SequenceTooLong { capacity: 8 }));
+    /// // A previous configuration left a non null Hash output divider: it is reset before the ramp
+    /// let mut bm1366 = BM1366::default();
+    /// bm1366.plls[BM1366_PLL_ID_HASH].set_out_div(BM1366_PLL_OUT_HASH, 3);
+    /// assert_eq!(bm1366.hash_freq(), HertzU64::kHz(12_500));
+    /// assert_eq!(bm1366.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x00, 0x00, 0x00, 0x00, 24], delay_ms: 2}));
+    /// assert_eq!(bm1366.registers.get(&PLL0Divider::ADDR).unwrap(), &0x0000_0000);
+    /// assert_eq!(bm1366.hash_freq(), HertzU64::MHz(50));
     /// ```
     fn set_hash_freq_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
         match self.seq_step {
@@ -1098,13 +1394,19 @@ impl Asic for BM1366 {
                             self.plls[BM1366_PLL_ID_HASH].parameter(),
                             Destination::All,
                         ),
-                        delay_ms: if freq > HertzU64::MHz(380) { 2300 } else { 400 },
+                        delay_ms: if freq > BM1366_HASH_FREQ_LONG_DELAY {
+                            2300
+                        } else {
+                            400
+                        },
                     })
                 }
             }
             _ => {
                 // authorize a SetHashFreq sequence start whatever the current step was
                 self.seq_step = SequenceStep::HashFreq(0);
+                // the ramp only programs the PLL0Parameter, so reset any output divider left by a previous
+                // configuration. Always written, even if already null, as seen on captured sequences.
                 self.plls[BM1366_PLL_ID_HASH].set_out_div(BM1366_PLL_OUT_HASH, 0);
                 self.registers
                     .insert(PLL0Divider::ADDR, self.plls[BM1366_PLL_ID_HASH].divider())
@@ -1141,6 +1443,8 @@ impl Asic for BM1366 {
 
     /// ## Send Enable Version Rolling command list
     ///
+    /// Nothing is sent if `mask` is rejected by `check_version_mask()`.
+    ///
     /// ### Example
     /// ```
     /// use bm1366::BM1366;
@@ -1150,8 +1454,14 @@ impl Asic for BM1366 {
     /// assert_eq!(bm1366.set_version_rolling_next(0x1fff_e000), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x10, 0x00, 0x00, 0x15, 0x1c, 0x02], delay_ms: 1}));
     /// assert_eq!(bm1366.set_version_rolling_next(0x1fff_e000), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0xa4, 0x90, 0x00, 0xff, 0xff, 0x1c], delay_ms: 1}));
     /// assert_eq!(bm1366.set_version_rolling_next(0x1fff_e000), None);
+    /// // 17 bits mask
+    /// let mut bm1366 = BM1366::default();
+    /// assert_eq!(bm1366.set_version_rolling_next(0x3fff_e000), None);
+    /// assert_eq!(bm1366.registers, BM1366::default().registers); // nothing programmed
     /// ```
     fn set_version_rolling_next(&mut self, mask: u32) -> Option<CmdDelay> {
+        // do not roll more version bits than the chips support
+        check_version_mask(mask).ok()?;
         match self.seq_step {
             SequenceStep::VersionRolling(step) => match step {
                 0 => {
@@ -1171,6 +1481,7 @@ impl Asic for BM1366 {
                 }
                 1 => {
                     self.seq_step = SequenceStep::None;
+                    debug_assert!(self.check_version_rolling(Some(mask)).is_ok());
                     None
                 }
                 _ => unreachable!(),
@@ -1189,4 +1500,34 @@ impl Asic for BM1366 {
             }
         }
     }
+
+    /// ## Get the Nonce layout
+    ///
+    /// Core ID is hardcoded in Nonce\[31:25\] and Small Core ID in Nonce\[24:22\].
+    /// If the Hardware Version Rolling is enabled, the Chip Address is hardcoded in Nonce\[24:17\],
+    /// else it is hardcoded in Nonce\[21:14\].
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1366::BM1366;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let layout = BM1366::default().nonce_layout();
+    /// assert_eq!(layout.core_id.extract(0x12345678), 0x09);
+    /// assert_eq!(layout.core_id.extract(0x906732c8), 72); // first Bitaxe Block 853742
+    /// let small_core_id = layout.small_core_id.unwrap();
+    /// assert_eq!(small_core_id.extract(0x12045678), 0);
+    /// assert_eq!(small_core_id.extract(0x12445678), 1);
+    /// assert_eq!(small_core_id.extract(0x13c45678), 7);
+    /// assert_eq!(layout.chip_addr.unwrap().extract(0x12345678), 0xD1);
+    /// assert_eq!(layout.chip_addr_rolling.unwrap().extract(0x12345679), 0x1A);
+    /// ```
+    fn nonce_layout(&self) -> NonceLayout {
+        NonceLayout {
+            core_id: NonceField::new(25, 7),
+            small_core_id: Some(NonceField::new(22, 3)),
+            chip_addr: Some(NonceField::new(14, 8)),
+            chip_addr_rolling: Some(NonceField::new(17, 8)),
+        }
+    }
 }
@@ -0,0 +1,32 @@
+extern crate bm1370;
+
+use bm1370::{BM1370, BM1370_PLL_ID_UART, BM1370_PLL_OUT_UART};
+use bm13xx_asic::register::PLL3Parameter;
+use bm13xx_protocol::command::{Command, Destination};
+use fugit::HertzU64;
+
+fn main() {
+    let mut bm1370 = BM1370::default();
+
+    // UART clock for some baudrates above the CLKI limit: 2 * baudrate * (BT8D + 1)
+    for (baudrate, bt8d) in [(6_250_000u64, 7u64), (6_000_000, 7), (12_500_000, 3)] {
+        let target = HertzU64::Hz(2 * baudrate * (bt8d + 1));
+        bm1370.configure_uart_pll(target);
+
+        let pll3_param = *bm1370.registers.get(&PLL3Parameter::ADDR).unwrap();
+        let uart_freq =
+            bm1370.plls[BM1370_PLL_ID_UART].frequency(bm1370.input_clock_freq, BM1370_PLL_OUT_UART);
+        println!(
+            "{} baud: target {}, UART PLL {}, PLL3Parameter {:#010x}, DIV4 {}",
+            baudrate,
+            target,
+            uart_freq,
+            pll3_param,
+            bm1370.plls[BM1370_PLL_ID_UART].out_div(BM1370_PLL_OUT_UART)
+        );
+        println!(
+            ">> {:x?}",
+            Command::write_reg(PLL3Parameter::ADDR, pll3_param, Destination::All)
+        );
+    }
+}
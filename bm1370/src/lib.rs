@@ -4,7 +4,15 @@
 #![macro_use]
 pub(crate) mod fmt;
 
-use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay, SequenceStep};
+use bm13xx_asic::{
+    check_addr_interval, check_version_mask, chip_addr_checked, chip_address,
+    core_register::*,
+    max_clki_baudrate,
+    nonce::{NonceField, NonceLayout},
+    pll::{pll_frequency, pll_roles, PllRole},
+    register::*,
+    Asic, BringUpConfig, BringUpStep, ChipSnapshot, CmdDelay, SequenceStep, BRING_UP_ORDER,
+};
 use bm13xx_protocol::command::{Command, Destination};
 
 use fugit::HertzU64;
@@ -12,6 +20,21 @@ use heapless::FnvIndexMap;
 
 pub const BM1370_CHIP_ID: u16 = 0x1370;
 pub const BM1370_CORE_CNT: usize = 128;
+/// Number of enabled Small Cores.
+///
+/// The Core layout gives `BM1370_CORE_CNT * BM1370_CORE_SMALL_CORE_CNT = 2048` Small Cores, but only
+/// 2040 of them are hashing (8 are disabled),
+/// matching the count used by other mining firmwares.
+/// This is the count used for the theoretical Hashrate.
+///
+/// ### Example
+///
+/// ```
+/// use bm1370::{BM1370_CORE_CNT, BM1370_CORE_SMALL_CORE_CNT, BM1370_SMALL_CORE_CNT};
+///
+/// assert_eq!(BM1370_CORE_CNT * BM1370_CORE_SMALL_CORE_CNT, 2048);
+/// assert_eq!(BM1370_CORE_CNT * BM1370_CORE_SMALL_CORE_CNT - BM1370_SMALL_CORE_CNT, 8);
+/// ```
 pub const BM1370_SMALL_CORE_CNT: usize = 2040;
 pub const BM1370_CORE_SMALL_CORE_CNT: usize = 16;
 pub const BM1370_DOMAIN_CNT: usize = 4;
@@ -20,9 +43,333 @@ pub const BM1370_PLL_ID_HASH: usize = 0; // PLL0 is used for Hashing
 pub const BM1370_PLL_OUT_HASH: usize = 0; // specifically PLL0_OUT0 is used for Hashing
 pub const BM1370_PLL_ID_UART: usize = 3; // PLL3 can be used for UART Baudrate
 pub const BM1370_PLL_OUT_UART: usize = 4; // specifically PLL3_OUT4 can be used for UART Baudrate
+/// Role of each PLL.
+///
+/// ### Example
+/// ```
+/// use bm1370::{BM1370_PLL_ID_HASH, BM1370_PLL_ID_UART, BM1370_PLL_ROLES};
+/// use bm13xx_asic::pll::PllRole;
+///
+/// assert_eq!(BM1370_PLL_ROLES, [PllRole::Hash, PllRole::Auxiliary, PllRole::Auxiliary, PllRole::Uart]);
+/// assert_eq!(BM1370_PLL_ROLES[BM1370_PLL_ID_HASH], PllRole::Hash);
+/// assert_eq!(BM1370_PLL_ROLES[BM1370_PLL_ID_UART], PllRole::Uart);
+/// ```
+pub const BM1370_PLL_ROLES: [PllRole; BM1370_PLL_CNT] =
+    pll_roles(BM1370_PLL_ID_HASH, BM1370_PLL_ID_UART);
+pub const BM1370_HASH_FREQ_MAX_STEP: HertzU64 = HertzU64::MHz(25); // largest timed ramp step
+
+// Enabled Small Cores can not exceed the Core layout
+const _: () = core::assert!(BM1370_SMALL_CORE_CNT <= BM1370_CORE_CNT * BM1370_CORE_SMALL_CORE_CNT);
+
+// Core ID, Small Core ID and Chip Address must fit in the Nonce
+const _: () = core::assert!(bm13xx_asic::nonce::nonce_chip_addr_shift(
+    BM1370_CORE_CNT.ilog2() + 1,
+    BM1370_CORE_SMALL_CORE_CNT.ilog2() + 1
+)
+.is_some());
+
+/// Capacity of the registers map.
+pub const BM1370_REGISTER_CAPACITY: usize = 128;
+/// Minimum number of registers map slots left free for user-added registers.
+pub const BM1370_REGISTER_MIN_HEADROOM: usize = 16;
+/// Number of registers set by `reset()`.
+///
+/// The remaining `BM1370_REGISTER_CAPACITY - BM1370_DEFAULT_REGISTER_CNT` slots are free for
+/// user-added registers, at least `BM1370_REGISTER_MIN_HEADROOM`.
+///
+/// ### Example
+/// ```
+/// use bm1370::{BM1370, BM1370_DEFAULT_REGISTER_CNT, BM1370_REGISTER_CAPACITY};
+///
+/// let bm1370 = BM1370::default();
+/// assert_eq!(bm1370.registers.len(), BM1370_DEFAULT_REGISTER_CNT);
+/// assert_eq!(bm1370.registers.capacity(), BM1370_REGISTER_CAPACITY);
+/// assert_eq!(BM1370_REGISTER_CAPACITY - BM1370_DEFAULT_REGISTER_CNT, 65); // headroom
+/// ```
+pub const BM1370_DEFAULT_REGISTER_CNT: usize = 63;
+
+// The default registers must fit in the registers map, with room left for user-added registers
+const _: () = core::assert!(
+    BM1370_DEFAULT_REGISTER_CNT + BM1370_REGISTER_MIN_HEADROOM <= BM1370_REGISTER_CAPACITY
+);
+
+/// Registers value set by `reset()`, as `(ADDR, value)`.
+///
+/// ### Example
+/// ```
+/// use bm1370::{BM1370_DEFAULT_REGISTERS, BM1370_REGISTER_CAPACITY};
+/// use bm13xx_asic::register::sorted_registers;
+/// use heapless::FnvIndexMap;
+///
+/// let regs: FnvIndexMap<u8, u32, BM1370_REGISTER_CAPACITY> =
+///     BM1370_DEFAULT_REGISTERS.into_iter().collect();
+/// assert_eq!(regs.len(), BM1370_DEFAULT_REGISTERS.len()); // no duplicated address
+/// // power-on values, by address
+/// assert_eq!(
+///     sorted_registers(&regs).as_slice(),
+///     &[
+///         (0x00, 0x1370_0000), (0x04, 0x0000_0000), (0x08, 0xc054_0165), (0x0c, 0x0000_0000),
+///         (0x10, 0x0000_0000), (0x14, 0x0000_0000), (0x18, 0x0000_c100), (0x1c, 0x0000_0000),
+///         (0x20, 0x0000_0007), (0x24, 0x0010_0000), (0x28, 0x0130_1a00), (0x2c, 0x000f_0000),
+///         (0x30, 0x0000_0080), (0x34, 0x0000_0000), (0x38, 0x0000_0000), (0x3c, 0x0000_0000),
+///         (0x40, 0x0802_0100), (0x44, 0x0000_0000), (0x48, 0x0000_0000), (0x4c, 0x0000_0000),
+///         (0x50, 0x0000_0000), (0x54, 0x0000_0000), (0x58, 0x0001_2111), (0x5c, 0x0000_ffff),
+///         (0x60, 0x2050_0174), (0x64, 0x2050_0174), (0x68, 0x0000_0000), (0x6c, 0x0001_0200),
+///         (0x70, 0x0000_0000), (0x74, 0x0000_0000), (0x78, 0x0000_0000), (0x7c, 0x0000_0000),
+///         (0x80, 0x0000_0000), (0x84, 0x0000_0000), (0x8c, 0x0000_0000), (0x90, 0x0000_0000),
+///         (0x94, 0x0000_0000), (0x98, 0x0000_0000), (0x9c, 0x00f7_0073), (0xa0, 0x0000_0000),
+///         (0xa4, 0x0000_ffff), (0xa8, 0x0007_0000), (0xac, 0x0000_0000), (0xb0, 0x0000_0000),
+///         (0xb4, 0x0000_0000), (0xb8, 0x2000_0000), (0xbc, 0x0000_3313), (0xc0, 0x0000_2000),
+///         (0xc4, 0x0000_b850), (0xc8, 0x0000_0000), (0xcc, 0x0000_0000), (0xd0, 0x0000_0000),
+///         (0xd4, 0x0000_0000), (0xd8, 0x0000_0000), (0xdc, 0x0000_0000), (0xe0, 0x0000_0000),
+///         (0xe4, 0x0000_0000), (0xe8, 0x0000_0000), (0xec, 0x0000_0000), (0xf0, 0x0000_0000),
+///         (0xf4, 0x0000_0000), (0xf8, 0x0000_0000), (0xfc, 0x0000_0000),
+///     ]
+/// );
+/// ```
+pub const BM1370_DEFAULT_REGISTERS: [(u8, u32); BM1370_DEFAULT_REGISTER_CNT] = [
+    (ChipIdentification::ADDR, 0x1370_0000),
+    (HashRate::ADDR, 0x0000_0000),
+    (PLL0Parameter::ADDR, 0xc054_0165),
+    (ChipNonceOffsetV2::ADDR, 0x0000_0000),
+    (HashCountingNumber::ADDR, 0x0000_0000),
+    (TicketMask::ADDR, 0x0000_0000),
+    (MiscControl::ADDR, 0x0000_c100),
+    (I2CControl::ADDR, 0x0000_0000),
+    (OrderedClockEnable::ADDR, 0x0000_0007),
+    (Reg24::ADDR, 0x0010_0000),
+    (FastUARTConfigurationV2::ADDR, 0x0130_1a00),
+    (UARTRelay::ADDR, 0x000f_0000),
+    (Reg30::ADDR, 0x0000_0080),
+    (Reg34::ADDR, 0x0000_0000),
+    (TicketMask2::ADDR, 0x0000_0000),
+    (CoreRegisterControl::ADDR, 0x0000_0000),
+    (CoreRegisterValue::ADDR, 0x0802_0100),
+    (ExternalTemperatureSensorRead::ADDR, 0x0000_0000),
+    (ErrorFlag::ADDR, 0x0000_0000),
+    (NonceErrorCounter::ADDR, 0x0000_0000),
+    (NonceOverflowCounter::ADDR, 0x0000_0000),
+    (AnalogMuxControlV2::ADDR, 0x0000_0000),
+    (IoDriverStrenghtConfiguration::ADDR, 0x0001_2111),
+    (TimeOut::ADDR, 0x0000_FFFF),
+    (PLL1Parameter::ADDR, 0x2050_0174),
+    (PLL2Parameter::ADDR, 0x2050_0174),
+    (PLL3Parameter::ADDR, 0x0000_0000),
+    (OrderedClockMonitor::ADDR, 0x0001_0200),
+    (PLL0Divider::ADDR, 0x0000_0000),
+    (PLL1Divider::ADDR, 0x0000_0000),
+    (PLL2Divider::ADDR, 0x0000_0000),
+    (PLL3Divider::ADDR, 0x0000_0000),
+    (ClockOrderControl0::ADDR, 0x0000_0000),
+    (ClockOrderControl1::ADDR, 0x0000_0000),
+    (ClockOrderStatus::ADDR, 0x0000_0000),
+    (FrequencySweepControl1::ADDR, 0x0000_0000),
+    (GoldenNonceForSweepReturn::ADDR, 0x0000_0000),
+    (ReturnedGroupPatternStatus::ADDR, 0x0000_0000),
+    (NonceReturnedTimeout::ADDR, 0x00f7_0073),
+    (ReturnedSinglePatternStatus::ADDR, 0x0000_0000),
+    (VersionRolling::ADDR, 0x0000_ffff),
+    (RegA8::ADDR, 0x0007_0000),
+    (RegAC::ADDR, 0x0000_0000),
+    (RegB0::ADDR, 0x0000_0000),
+    (RegB4::ADDR, 0x0000_0000),
+    (RegB8::ADDR, 0x2000_0000),
+    (RegBC::ADDR, 0x0000_3313),
+    (RegC0::ADDR, 0x0000_2000),
+    (RegC4::ADDR, 0x0000_b850),
+    (RegC8::ADDR, 0x0000_0000),
+    (RegCC::ADDR, 0x0000_0000),
+    (RegD0::ADDR, 0x0000_0000),
+    (RegD4::ADDR, 0x0000_0000),
+    (RegD8::ADDR, 0x0000_0000),
+    (RegDC::ADDR, 0x0000_0000),
+    (RegE0::ADDR, 0x0000_0000),
+    (RegE4::ADDR, 0x0000_0000),
+    (RegE8::ADDR, 0x0000_0000),
+    (RegEC::ADDR, 0x0000_0000),
+    (RegF0::ADDR, 0x0000_0000),
+    (RegF4::ADDR, 0x0000_0000),
+    (RegF8::ADDR, 0x0000_0000),
+    (RegFC::ADDR, 0x0000_0000),
+];
+
+/// Suggested capacity to collect a whole chain init, see `BM1370::max_init_frames()`.
+pub const BM1370_CHAIN_INIT_CAPACITY: usize = 2048;
+
+// The init of the largest documented chain (S21 XP, 13 domains of 7 chips) must fit
+const _: () = core::assert!(BM1370::max_init_frames(13, 13 * 7) <= BM1370_CHAIN_INIT_CAPACITY);
+
+/// Registers read back by the command sequences, they must be present in the registers map.
+pub const BM1370_REQUIRED_REGISTERS: [u8; 10] = [
+    TicketMask::ADDR,
+    MiscControlV2::ADDR,
+    FastUARTConfigurationV2::ADDR,
+    UARTRelay::ADDR,
+    AnalogMuxControlV2::ADDR,
+    IoDriverStrenghtConfiguration::ADDR,
+    PLL3Parameter::ADDR,
+    RegA8::ADDR,
+    VersionRolling::ADDR,
+    OrderedClockEnable::ADDR,
+];
+
+/// # BM1370 Core Error Statistics
+///
+/// ### Example
+/// ```
+/// use bm1370::BM1370CoreErrorStats;
+/// use bm13xx_asic::nonce::CoreStats;
+///
+/// let mut stats = BM1370CoreErrorStats::new();
+/// for (core_id, valid) in [(72, true), (72, false), (9, true), (127, false), (127, false), (128, false)] {
+///     stats.record(core_id, valid);
+/// }
+/// assert_eq!(stats.core(72), Some(CoreStats { submitted: 2, errors: 1 }));
+/// assert_eq!(stats.core(9), Some(CoreStats { submitted: 1, errors: 0 }));
+/// assert_eq!(stats.core(127), Some(CoreStats { submitted: 2, errors: 2 }));
+/// assert_eq!(stats.core(128), None);
+/// assert_eq!(stats.worst_cores::<1>()[0].0, 127);
+/// ```
+pub type BM1370CoreErrorStats = bm13xx_asic::nonce::CoreErrorStats<BM1370_CORE_CNT>;
+
+/// # Board Variant
+///
+/// Antminer boards using the BM1370 which are known to program some registers differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BoardVariant {
+    /// Antminer S21 Pro
+    S21Pro,
+    /// Antminer S21 XP
+    S21XP,
+}
+
+impl BoardVariant {
+    /// ## Get the ClockDelayCtrl core register value used by this variant
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BoardVariant;
+    ///
+    /// assert_eq!(BoardVariant::S21Pro.clock_delay_ctrl(), 0x0c);
+    /// assert_eq!(BoardVariant::S21XP.clock_delay_ctrl(), 0x10);
+    /// ```
+    pub const fn clock_delay_ctrl(&self) -> u8 {
+        match self {
+            // Seems to be a ClockDelayCtrlV3 ? because 0x0c has a 1 in bit2 which is not in ClockDelayCtrlV2
+            // S21XP is ClockDelayCtrlV2 with ccdly=0, pwth=2 and sweep frequency mode disabled
+            BoardVariant::S21Pro => 0x0c,
+            BoardVariant::S21XP => 0x10,
+        }
+    }
+
+    /// ## Get the HashCountingNumber register value used by this variant
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BoardVariant;
+    ///
+    /// assert_eq!(BoardVariant::S21Pro.hash_counting_number(), 0x0000_1eb5);
+    /// assert_eq!(BoardVariant::S21XP.hash_counting_number(), 0x0000_1a44);
+    /// ```
+    pub const fn hash_counting_number(&self) -> u32 {
+        match self {
+            BoardVariant::S21Pro => 0x0000_1eb5,
+            BoardVariant::S21XP => 0x0000_1a44,
+        }
+    }
+}
+
+/// ## Detect the Board Variant from registers read back from a chip
+///
+/// The fingerprint is the HashCountingNumber register, which is set to a per-variant value.
+/// Returns `None` if the register is missing or its value is not known.
+///
+/// ### Example
+/// ```
+/// use bm1370::{detect_variant, BoardVariant, BM1370_REGISTER_CAPACITY};
+/// use bm13xx_asic::register::*;
+/// use heapless::FnvIndexMap;
+///
+/// let mut regs = FnvIndexMap::<u8, u32, BM1370_REGISTER_CAPACITY>::new();
+/// assert_eq!(detect_variant(&regs), None);
+/// regs.insert(HashCountingNumber::ADDR, 0x0000_1eb5).unwrap();
+/// assert_eq!(detect_variant(&regs), Some(BoardVariant::S21Pro));
+/// regs.insert(HashCountingNumber::ADDR, 0x0000_1a44).unwrap();
+/// assert_eq!(detect_variant(&regs), Some(BoardVariant::S21XP));
+/// regs.insert(HashCountingNumber::ADDR, 0x0000_0000).unwrap();
+/// assert_eq!(detect_variant(&regs), None);
+/// ```
+pub fn detect_variant(
+    read_regs: &FnvIndexMap<u8, u32, BM1370_REGISTER_CAPACITY>,
+) -> Option<BoardVariant> {
+    let hcn = *read_regs.get(&HashCountingNumber::ADDR)?;
+    [BoardVariant::S21Pro, BoardVariant::S21XP]
+        .into_iter()
+        .find(|variant| variant.hash_counting_number() == hcn)
+}
+
+/// # Hash Frequency Preset
+///
+/// Common Hash frequencies with a precomputed PLL0Parameter, to avoid running the solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum HashPreset {
+    /// 485 MHz
+    MHz485,
+    /// 525 MHz
+    MHz525,
+    /// 600 MHz
+    MHz600,
+}
+
+impl HashPreset {
+    /// All the known presets.
+    pub const ALL: [HashPreset; 3] = [HashPreset::MHz485, HashPreset::MHz525, HashPreset::MHz600];
+
+    /// ## Get the Hash frequency of this preset
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::HashPreset;
+    /// use fugit::HertzU64;
+    ///
+    /// assert_eq!(HashPreset::MHz525.freq(), HertzU64::MHz(525));
+    /// ```
+    pub const fn freq(&self) -> HertzU64 {
+        match self {
+            HashPreset::MHz485 => HertzU64::MHz(485),
+            HashPreset::MHz525 => HertzU64::MHz(525),
+            HashPreset::MHz600 => HertzU64::MHz(600),
+        }
+    }
+
+    /// ## Get the PLL0Parameter of this preset
+    ///
+    /// Precomputed for the default 25 MHz CLKI, with a PLL0_OUT0 divider of 0.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{HashPreset, BM1370, BM1370_PLL_ID_HASH};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// for preset in HashPreset::ALL {
+    ///     let mut bm1370 = BM1370::default();
+    ///     bm1370.set_hash_freq(preset.freq());
+    ///     assert_eq!(bm1370.plls[BM1370_PLL_ID_HASH].parameter(), preset.pll_parameter());
+    /// }
+    /// ```
+    pub const fn pll_parameter(&self) -> u32 {
+        match self {
+            HashPreset::MHz485 => 0xd0c2_0240,
+            HashPreset::MHz525 => 0xc0a8_0230,
+            HashPreset::MHz600 => 0xd0c0_0230,
+        }
+    }
+}
 
 /// # BM1370
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 // #[cfg_attr(feature = "defmt-03", derive(defmt::Format))] // FnvIndexMap doesn't implement defmt
 pub struct BM1370 {
     seq_step: SequenceStep,
@@ -35,9 +382,14 @@ pub struct BM1370 {
     pub input_clock_freq: HertzU64,
     pub plls: [bm13xx_asic::pll::Pll; BM1370_PLL_CNT],
     pub chip_addr: u8,
-    pub registers: FnvIndexMap<u8, u32, 64>,
+    pub registers: FnvIndexMap<u8, u32, BM1370_REGISTER_CAPACITY>,
     pub core_registers: FnvIndexMap<u8, u8, 16>,
+    /// Board Variant, selects the per-variant values in the command sequences.
+    /// If `None`, the S21 XP values are used, except for the HashCountingNumber using the S21 Pro one.
+    pub variant: Option<BoardVariant>,
     cno_interval: usize,
+    /// OrderedClockEnable value to restore when leaving idle
+    idle_clk_ord_en: Option<u32>,
 }
 
 impl BM1370 {
@@ -48,27 +400,750 @@ impl BM1370 {
         }
     }
 
+    /// ## Reset the whole Chip state to default, in place
+    ///
+    /// Like `reset()`, but also forget the board `variant`, so a pooled chip object can be reused
+    /// on another board without reconstructing it.
+    /// The `input_clock_freq` can not be told from the chip itself, it stays the one given to
+    /// `new_with_clk()`.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BoardVariant, BM1370};
+    /// use bm13xx_asic::{register::*, Asic};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// bm1370.variant = Some(BoardVariant::S21Pro);
+    /// bm1370.set_chip_addr(8);
+    /// bm1370.set_hash_freq(HertzU64::MHz(400));
+    /// bm1370.registers.insert(VersionRolling::ADDR, 0x9000_ffff).unwrap();
+    /// bm1370.init_next(256);
+    /// bm1370.reset_state();
+    /// let fresh = BM1370::default();
+    /// assert_eq!(bm1370.variant, fresh.variant);
+    /// assert_eq!(bm1370.chip_addr, fresh.chip_addr);
+    /// assert_eq!(bm1370.cno_interval(), fresh.cno_interval());
+    /// assert_eq!(bm1370.registers, fresh.registers);
+    /// assert_eq!(bm1370.core_registers, fresh.core_registers);
+    /// for (pll, fresh_pll) in bm1370.plls.iter().zip(fresh.plls.iter()) {
+    ///     assert_eq!(pll.parameter(), fresh_pll.parameter());
+    ///     assert_eq!(pll.divider(), fresh_pll.divider());
+    /// }
+    /// assert_eq!(bm1370.init_next(256), BM1370::default().init_next(256));
+    /// ```
+    pub fn reset_state(&mut self) {
+        self.reset_chip_state();
+        self.variant = None;
+    }
+
+    /// Reload the chip power-on defaults, shared by `reset()` and `reset_state()`.
+    fn reset_chip_state(&mut self) {
+        self.seq_step = SequenceStep::default();
+        self.sha = bm13xx_asic::sha::Sha::default();
+        self.plls = [bm13xx_asic::pll::Pll::default(); BM1370_PLL_CNT];
+        self.chip_addr = 0;
+        self.idle_clk_ord_en = None;
+        self.registers.clear();
+        self.core_registers.clear();
+        // Default PLLs Parameter
+        self.plls[0].set_parameter(0xC054_0165);
+        self.plls[1].set_parameter(0x2050_0174);
+        self.plls[2].set_parameter(0x2050_0174);
+        self.plls[3].set_parameter(0x0000_0000);
+        // Default PLLs Divider
+        self.plls[0].set_divider(0x0000_0000);
+        self.plls[1].set_divider(0x0000_0000);
+        self.plls[2].set_divider(0x0000_0000);
+        self.plls[3].set_divider(0x0000_0000);
+        // Default Registers Value
+        for (reg_addr, reg_value) in BM1370_DEFAULT_REGISTERS {
+            self.registers.insert(reg_addr, reg_value).unwrap();
+        }
+        // Default Core Registers Value
+        self.core_registers
+            .insert(ClockDelayCtrlV2::ID, 0x52)
+            .unwrap();
+        // self.core_registers.insert(1, 0x00).unwrap(); // not used anywhere in official FW
+        self.core_registers.insert(CoreReg2::ID, 0x55).unwrap();
+        // self.core_registers.insert(CoreError::ID, 0x00).unwrap();
+        // self.core_registers.insert(CoreEnable::ID, 0x08).unwrap();
+        self.core_registers.insert(HashClockCtrl::ID, 0x00).unwrap();
+        self.core_registers
+            .insert(HashClockCounter::ID, 0x08)
+            .unwrap();
+        // self
+        //     .core_registers
+        //     .insert(SweepClockCtrl::ID, 0x00)
+        //     .unwrap();
+        self.core_registers.insert(CoreReg8::ID, 0x00).unwrap();
+        // self.core_registers.insert(9, 0x00).unwrap();
+        // self.core_registers.insert(9, 0x00).unwrap();
+        self.core_registers.insert(CoreReg11::ID, 0x00).unwrap();
+        // self.core_registers.insert(12, 0x00).unwrap();
+        // self.core_registers.insert(13, 0x15).unwrap();
+        // self.core_registers.insert(14, 0x00).unwrap();
+        // self.core_registers.insert(15, 0x00).unwrap();
+        // self.core_registers.insert(16, 0xbe).unwrap();
+        // self.core_registers.insert(17, 0x00).unwrap();
+        // self.core_registers.insert(18, 0x00).unwrap();
+        // self.core_registers.insert(19, 0x00).unwrap();
+        // self.core_registers.insert(20, 0x00).unwrap();
+        // self.core_registers.insert(21, 0x00).unwrap();
+        self.core_registers.insert(CoreReg22::ID, 0x00).unwrap();
+        // self.core_registers.insert(23, 0x80).unwrap();
+        self.cno_interval = 0;
+    }
+
+    /// ## Create a BM1370 from an explicit registers map
+    ///
+    /// The PLLs are configured from the PLL Parameter and Divider registers found in the map.
+    ///
+    /// ### Errors
+    ///
+    /// - Missing register if one of the `BM1370_REQUIRED_REGISTERS` is not in the map
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_PLL_ID_HASH, BM1370_REGISTER_CAPACITY, BM1370_REQUIRED_REGISTERS};
+    /// use bm13xx_asic::{register::*, Asic, Error};
+    /// use fugit::HertzU64;
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut regs = FnvIndexMap::<u8, u32, BM1370_REGISTER_CAPACITY>::new();
+    /// for reg_addr in BM1370_REQUIRED_REGISTERS {
+    ///     regs.insert(reg_addr, 0x0000_0000).unwrap();
+    /// }
+    /// regs.insert(PLL0Parameter::ADDR, 0xc054_0165).unwrap();
+    /// let bm1370 = BM1370::from_registers(HertzU64::MHz(25), regs.clone()).unwrap();
+    /// assert_eq!(bm1370.registers.len(), BM1370_REQUIRED_REGISTERS.len() + 1);
+    /// assert_eq!(bm1370.plls[BM1370_PLL_ID_HASH].parameter(), 0xc054_0165);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(50));
+    ///
+    /// regs.remove(&TicketMask::ADDR);
+    /// assert_eq!(
+    ///     BM1370::from_registers(HertzU64::MHz(25), regs).unwrap_err(),
+    ///     Error::MissingRegister { reg_addr: TicketMask::ADDR }
+    /// );
+    /// ```
+    pub fn from_registers(
+        clk: HertzU64,
+        registers: FnvIndexMap<u8, u32, BM1370_REGISTER_CAPACITY>,
+    ) -> bm13xx_asic::Result<Self> {
+        if let Some(reg_addr) = BM1370_REQUIRED_REGISTERS
+            .into_iter()
+            .find(|reg_addr| !registers.contains_key(reg_addr))
+        {
+            return Err(bm13xx_asic::Error::MissingRegister { reg_addr });
+        }
+        let mut bm1370 = Self::new_with_clk(clk);
+        let pll_param_addr = [
+            PLL0Parameter::ADDR,
+            PLL1Parameter::ADDR,
+            PLL2Parameter::ADDR,
+            PLL3Parameter::ADDR,
+        ];
+        let pll_div_addr = [
+            PLL0Divider::ADDR,
+            PLL1Divider::ADDR,
+            PLL2Divider::ADDR,
+            PLL3Divider::ADDR,
+        ];
+        for (pll_id, pll) in bm1370.plls.iter_mut().enumerate() {
+            if let Some(parameter) = registers.get(&pll_param_addr[pll_id]) {
+                pll.set_parameter(*parameter);
+            }
+            if let Some(divider) = registers.get(&pll_div_addr[pll_id]) {
+                pll.set_divider(*divider);
+            }
+        }
+        bm1370.registers = registers;
+        Ok(bm1370)
+    }
+
     /// ## Set the Chip Address
     ///
     /// ### Example
     /// ```
-    /// use bm1370::BM1370;
+    /// use bm1370::BM1370;
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// bm1370.set_chip_addr(2);
+    /// assert_eq!(bm1370.chip_addr, 2);
+    /// ```
+    pub fn set_chip_addr(&mut self, chip_addr: u8) {
+        self.chip_addr = chip_addr;
+    }
+
+    /// ## Compare the registers against a live register dump
+    ///
+    /// Return the `(addr, expected, live)` registers whose `live` value, read back from the chip,
+    /// differs from the expected one in the `registers` map, sorted by address.
+    /// Registers missing from the `live` dump are not compared.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_REGISTER_CAPACITY};
+    /// use bm13xx_asic::register::*;
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let bm1370 = BM1370::default();
+    /// let mut live: FnvIndexMap<u8, u32, BM1370_REGISTER_CAPACITY> =
+    ///     bm1370.registers.iter().map(|(&reg_addr, &reg_val)| (reg_addr, reg_val)).collect();
+    /// assert!(bm1370.compare_registers(&live).is_empty());
+    /// live.insert(TicketMask::ADDR, 0x0000_00ff).unwrap(); // corrupted
+    /// live.remove(&VersionRolling::ADDR); // not read
+    /// assert_eq!(
+    ///     bm1370.compare_registers(&live).as_slice(),
+    ///     &[(TicketMask::ADDR, 0x0000_0000, 0x0000_00ff)]
+    /// );
+    /// ```
+    pub fn compare_registers(
+        &self,
+        live: &FnvIndexMap<u8, u32, BM1370_REGISTER_CAPACITY>,
+    ) -> heapless::Vec<(u8, u32, u32), BM1370_REGISTER_CAPACITY> {
+        sorted_registers(&self.registers)
+            .into_iter()
+            .filter_map(|(reg_addr, expected)| match live.get(&reg_addr) {
+                Some(&live_val) if live_val != expected => Some((reg_addr, expected, live_val)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// ## Get the programmed Version Rolling mask
+    ///
+    /// Return `None` if Hardware Version Rolling is disabled.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// assert_eq!(bm1370.version_mask(), None);
+    /// while bm1370.set_version_rolling_next(0x1fff_e000).is_some() {}
+    /// assert_eq!(bm1370.version_mask(), Some(0x1fff_e000));
+    /// ```
+    pub fn version_mask(&self) -> Option<u32> {
+        let vers_roll = VersionRolling(*self.registers.get(&VersionRolling::ADDR).unwrap_or(&0));
+        vers_roll.enabled().then_some(vers_roll.mask())
+    }
+
+    /// ## Set the Hash Frequency from a preset
+    ///
+    /// Apply the precomputed PLL0Parameter of the preset without running the solver, with a null
+    /// Hash output divider. The presets are computed for the default 25 MHz CLKI, see
+    /// `HashPreset::pll_parameter()`: with another `input_clock_freq`, the dividers are solved
+    /// for the preset frequency like `set_hash_freq()` does.
+    /// The PLL0Parameter and PLL0Divider registers are kept in sync.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{HashPreset, BM1370, BM1370_PLL_ID_HASH};
+    /// use bm13xx_asic::{register::*, Asic};
+    /// use fugit::HertzU64;
+    ///
+    /// for preset in HashPreset::ALL {
+    ///     let mut bm1370 = BM1370::default();
+    ///     bm1370.preset_hash_freq(preset);
+    ///     assert_eq!(bm1370.hash_freq(), preset.freq());
+    ///     assert_eq!(bm1370.plls[BM1370_PLL_ID_HASH].parameter(), preset.pll_parameter());
+    ///     assert_eq!(bm1370.registers.get(&PLL0Parameter::ADDR), Some(&preset.pll_parameter()));
+    ///     assert_eq!(bm1370.registers.get(&PLL0Divider::ADDR), Some(&0x0000_0000));
+    ///
+    ///     let mut bm1370 = BM1370::new_with_clk(HertzU64::MHz(50));
+    ///     bm1370.preset_hash_freq(preset);
+    ///     assert_eq!(bm1370.hash_freq(), preset.freq());
+    ///     let parameter = bm1370.plls[BM1370_PLL_ID_HASH].parameter();
+    ///     assert_eq!(bm1370.registers.get(&PLL0Parameter::ADDR), Some(&parameter));
+    /// }
+    /// ```
+    pub fn preset_hash_freq(&mut self, preset: HashPreset) -> &mut Self {
+        if self.input_clock_freq == HertzU64::MHz(25) {
+            self.plls[BM1370_PLL_ID_HASH]
+                .set_parameter(preset.pll_parameter())
+                .set_out_div(BM1370_PLL_OUT_HASH, 0);
+        } else {
+            self.plls[BM1370_PLL_ID_HASH].set_out_div(BM1370_PLL_OUT_HASH, 0);
+            self.set_hash_freq(preset.freq());
+        }
+        self.registers
+            .insert(
+                PLL0Parameter::ADDR,
+                self.plls[BM1370_PLL_ID_HASH].parameter(),
+            )
+            .unwrap();
+        self.registers
+            .insert(PLL0Divider::ADDR, self.plls[BM1370_PLL_ID_HASH].divider())
+            .unwrap();
+        self
+    }
+
+    /// ## Get a Hash Frequency ramp fitting a given duration
+    ///
+    /// Like `set_hash_freq_next()`, the Hash output divider is first reset (2 ms delay), then
+    /// PLL0Parameter is written at each step, the last step landing on `target`.
+    /// The ramp uses 6.25 MHz steps, but fewer and larger steps if each one would get less than the
+    /// 400 ms settling delay used by `set_hash_freq_next()`, or if they would not fit in `N - 1`.
+    /// A step is never larger than `BM1370_HASH_FREQ_MAX_STEP`, a short `total` then gives shorter
+    /// delays instead. `total` is evenly split between the steps, the remainder going to the last
+    /// one, so the summed delays match `total` to the millisecond. The ramp can go up or down.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the ramp does not fit in `N` commands with steps of at most
+    ///   `BM1370_HASH_FREQ_MAX_STEP`, the chip is then left untouched
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{Asic, Error};
+    /// use core::time::Duration;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// let seq = bm1370.set_hash_freq_timed::<64>(HertzU64::MHz(500), Duration::from_secs(10)).unwrap();
+    /// assert_eq!(seq.len(), 1 + 24); // 10 s only leaves room for 24 steps of 400 ms
+    /// assert_eq!(seq.iter().map(|c| c.delay_ms).sum::<u32>(), 10_000);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(500));
+    /// let seq = bm1370.set_hash_freq_timed::<64>(HertzU64::MHz(475), Duration::from_secs(60)).unwrap();
+    /// assert_eq!(seq.len(), 1 + 4); // 6.25 MHz steps
+    /// assert_eq!(seq.iter().map(|c| c.delay_ms).sum::<u32>(), 60_000);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(475));
+    /// let seq = bm1370.set_hash_freq_timed::<8>(HertzU64::MHz(525), Duration::from_millis(60_001)).unwrap();
+    /// assert_eq!(seq.len(), 8); // 7 steps due to the capacity
+    /// assert_eq!(seq.iter().map(|c| c.delay_ms).sum::<u32>(), 60_001);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(525));
+    /// let seq = bm1370.set_hash_freq_timed::<64>(HertzU64::MHz(75), Duration::from_secs(1)).unwrap();
+    /// assert_eq!(seq.len(), 1 + 18); // 25 MHz steps at most
+    /// assert_eq!(seq.iter().map(|c| c.delay_ms).sum::<u32>(), 1_000);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(75));
+    ///
+    /// let before = bm1370.clone();
+    /// assert_eq!(
+    ///     bm1370.set_hash_freq_timed::<8>(HertzU64::MHz(500), Duration::from_secs(60)),
+    ///     Err(Error::SequenceTooLong { capacity: 8 })
+    /// );
+    /// assert_eq!(bm1370.hash_freq(), before.hash_freq()); // left untouched
+    /// assert_eq!(bm1370.registers, before.registers);
+    /// ```
+    pub fn set_hash_freq_timed<const N: usize>(
+        &mut self,
+        target: HertzU64,
+        total: core::time::Duration,
+    ) -> bm13xx_asic::Result<heapless::Vec<CmdDelay, N>> {
+        let total_ms = total.as_millis().min(u32::MAX as u128) as u32;
+        let ramp_ms = total_ms.saturating_sub(2);
+        let from = self.hash_freq().raw() as i128;
+        let delta = target.raw() as i128 - from;
+        let min_steps = (delta
+            .unsigned_abs()
+            .div_ceil(BM1370_HASH_FREQ_MAX_STEP.raw() as u128) as usize)
+            .max(1);
+        let steps = (delta
+            .unsigned_abs()
+            .div_ceil(HertzU64::kHz(6250).raw() as u128) as usize)
+            .min((ramp_ms / 400) as usize)
+            .min(N.saturating_sub(1))
+            .max(min_steps);
+        if steps + 1 > N {
+            return Err(bm13xx_asic::Error::SequenceTooLong { capacity: N });
+        }
+        let mut seq = heapless::Vec::new();
+        self.plls[BM1370_PLL_ID_HASH].set_out_div(BM1370_PLL_OUT_HASH, 0);
+        self.registers
+            .insert(PLL0Divider::ADDR, self.plls[BM1370_PLL_ID_HASH].divider())
+            .unwrap();
+        seq.push(CmdDelay {
+            cmd: Command::write_reg(
+                PLL0Divider::ADDR,
+                self.plls[BM1370_PLL_ID_HASH].divider(),
+                Destination::All,
+            ),
+            delay_ms: total_ms.min(2),
+        })
+        .map_err(|_| bm13xx_asic::Error::SequenceTooLong { capacity: N })?;
+        for step in 1..=steps {
+            let freq = from + delta * step as i128 / steps as i128;
+            self.set_hash_freq(HertzU64::from_raw(freq as u64));
+            let parameter = self.plls[BM1370_PLL_ID_HASH].parameter();
+            self.registers
+                .insert(PLL0Parameter::ADDR, parameter)
+                .unwrap();
+            seq.push(CmdDelay {
+                cmd: Command::write_reg(PLL0Parameter::ADDR, parameter, Destination::All),
+                delay_ms: if step == steps {
+                    ramp_ms - ramp_ms / steps as u32 * (steps as u32 - 1)
+                } else {
+                    ramp_ms / steps as u32
+                },
+            })
+            .map_err(|_| bm13xx_asic::Error::SequenceTooLong { capacity: N })?;
+        }
+        Ok(seq)
+    }
+
+    /// ## Set the Hash PLL dividers
+    ///
+    /// Bypass the frequency solver and program the Hash PLL with the given dividers, keeping
+    /// the current output divider. The dividers are checked, see `PllDividers::check()`.
+    /// Returns the PLL0Parameter write command.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{pll::PllDividers, Asic, CmdDelay, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// let cmd = bm1370.set_hash_pll_dividers(192, 2, 5, 1).unwrap(); // VCO at 2400 MHz, high band
+    /// assert_eq!(cmd, CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xd0, 0xc0, 0x02, 0x51, 0x13], delay_ms: 400});
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(200));
+    /// assert_eq!(
+    ///     bm1370.set_hash_pll_dividers(192, 2, 1, 5),
+    ///     Err(Error::InvalidPllDividers {
+    ///         dividers: PllDividers { fb_div: 192, ref_div: 2, post1_div: 1, post2_div: 5, out_div: 0 }
+    ///     })
+    /// );
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(200)); // unchanged
+    /// ```
+    pub fn set_hash_pll_dividers(
+        &mut self,
+        fb_div: u16,
+        ref_div: u8,
+        post1_div: u8,
+        post2_div: u8,
+    ) -> bm13xx_asic::Result<CmdDelay> {
+        let pll = &mut self.plls[BM1370_PLL_ID_HASH];
+        let dividers = bm13xx_asic::pll::PllDividers {
+            fb_div,
+            ref_div,
+            post1_div,
+            post2_div,
+            out_div: pll.out_div(BM1370_PLL_OUT_HASH),
+        };
+        dividers.check(self.input_clock_freq)?;
+        pll.set_dividers(BM1370_PLL_OUT_HASH, &dividers).enable();
+        if pll.vco_freq(self.input_clock_freq) >= HertzU64::MHz(2400) {
+            pll.set_vco_high_freq();
+        } else {
+            pll.set_vco_low_freq();
+        }
+        self.registers
+            .insert(PLL0Parameter::ADDR, pll.parameter())
+            .unwrap();
+        Ok(CmdDelay {
+            cmd: Command::write_reg(PLL0Parameter::ADDR, pll.parameter(), Destination::All),
+            delay_ms: 400,
+        })
+    }
+
+    /// ## Enable or disable a single Core command
+    ///
+    /// Write the CoreEnable core register of the Core `core_id` in all chips, to stop a known-bad
+    /// Core from producing hardware errors. The RegB8 and RegBC roles are unknown, the CoreEnable
+    /// core register is the only known per-Core enable, and its polarity (CORE_EN_I set to enable)
+    /// has not been confirmed on hardware yet.
+    /// The value is Core specific so it is not saved in `core_registers`.
+    /// Returns `None` if `core_id` is out of range.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{core_register::*, register::*};
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// let step = bm1370.set_core_enabled(72, false).unwrap();
+    /// assert_eq!(step.cmd[5], CoreRegisterControl::ADDR);
+    /// assert_eq!(step.cmd[6..10], CoreRegisterControl::write_core_reg(72, CoreEnable(0x00)).to_be_bytes());
+    /// let step = bm1370.set_core_enabled(72, true).unwrap();
+    /// assert_eq!(step.cmd[6..10], [0x80, 0x48, 0x84, 0xff]);
+    /// assert_eq!(bm1370.set_core_enabled(128, false), None);
+    /// ```
+    pub fn set_core_enabled(&mut self, core_id: u8, enabled: bool) -> Option<CmdDelay> {
+        if core_id as usize >= BM1370_CORE_CNT {
+            return None;
+        }
+        let core_en = CoreEnable(0)
+            .set_core_en_i(if enabled { 0xff } else { 0x00 })
+            .val();
+        Some(CmdDelay {
+            cmd: Command::write_reg(
+                CoreRegisterControl::ADDR,
+                CoreRegisterControl::write_core_reg(core_id, CoreEnable(core_en)),
+                Destination::All,
+            ),
+            delay_ms: 10,
+        })
+    }
+
+    /// ClockDelayCtrlV2 core register value written by the init and core reset sequences.
+    /// If no `variant` is set, the S21 XP value is used.
+    fn clock_delay_ctrl(&self) -> u8 {
+        self.variant
+            .unwrap_or(BoardVariant::S21XP)
+            .clock_delay_ctrl()
+    }
+
+    /// ## Enter or leave the low-power idle command
+    ///
+    /// Entering idle gates all the ordered clocks with OrderedClockEnable to park the cores,
+    /// without a full reset. Leaving idle restores the OrderedClockEnable value saved when
+    /// entering it. Nothing is saved nor restored if already in the requested state.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{register::*, CmdDelay};
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// assert_eq!(bm1370.set_idle(true), CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x07], delay_ms: 10});
+    /// assert_eq!(bm1370.registers.get(&OrderedClockEnable::ADDR).unwrap(), &0x0000_0000);
+    /// assert_eq!(bm1370.set_idle(true).cmd[9], 0x00); // still idle
+    /// assert_eq!(bm1370.set_idle(false), CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x20, 0x00, 0x00, 0x00, 0x07, 0x1c], delay_ms: 10});
+    /// assert_eq!(bm1370.registers.get(&OrderedClockEnable::ADDR).unwrap(), &0x0000_0007);
+    /// ```
+    pub fn set_idle(&mut self, enable: bool) -> CmdDelay {
+        let current = *self.registers.get(&OrderedClockEnable::ADDR).unwrap();
+        let clk_ord_en = if enable {
+            self.idle_clk_ord_en.get_or_insert(current);
+            OrderedClockEnable(current).disable_all().val()
+        } else {
+            self.idle_clk_ord_en.take().unwrap_or(current)
+        };
+        self.registers
+            .insert(OrderedClockEnable::ADDR, clk_ord_en)
+            .unwrap();
+        CmdDelay {
+            cmd: Command::write_reg(OrderedClockEnable::ADDR, clk_ord_en, Destination::All),
+            delay_ms: 10,
+        }
+    }
+
+    /// ## Clear the Chip Nonce Offsets command list
+    ///
+    /// Write the default null ChipNonceOffsetV2 to each of the `chain_asic_num` chips, undoing
+    /// `split_nonce_between_chips_next()`, e.g. before reconfiguring the chain geometry.
+    ///
+    /// ### Errors
+    ///
+    /// - Invalid address interval if a chip has no valid address
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// while bm1370.split_nonce_between_chips_next(3, 2).is_some() {}
+    /// assert_ne!(bm1370.cno_interval(), 0);
+    /// let seq = bm1370.clear_nonce_offsets::<4>(3, 2).unwrap();
+    /// assert_eq!(
+    ///     seq,
+    ///     [
+    ///         CmdDelay { cmd: Command::write_reg(ChipNonceOffsetV2::ADDR, 0, Destination::Chip(0)), delay_ms: 0 },
+    ///         CmdDelay { cmd: Command::write_reg(ChipNonceOffsetV2::ADDR, 0, Destination::Chip(2)), delay_ms: 0 },
+    ///         CmdDelay { cmd: Command::write_reg(ChipNonceOffsetV2::ADDR, 0, Destination::Chip(4)), delay_ms: 0 },
+    ///     ]
+    /// );
+    /// assert_eq!(seq[1].cmd, [0x55, 0xaa, 0x41, 0x09, 0x02, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    /// assert_eq!(bm1370.cno_interval(), 0);
+    /// assert_eq!(bm1370.registers.get(&ChipNonceOffsetV2::ADDR).unwrap(), &0x0000_0000);
+    /// assert_eq!(bm1370.clear_nonce_offsets::<2>(3, 2), Err(Error::SequenceTooLong { capacity: 2 }));
+    /// assert_eq!(
+    ///     bm1370.clear_nonce_offsets::<4>(129, 2),
+    ///     Err(Error::InvalidAddrInterval { chip_count: 129, asic_addr_interval: 2 })
+    /// );
+    /// ```
+    pub fn clear_nonce_offsets<const N: usize>(
+        &mut self,
+        chain_asic_num: usize,
+        asic_addr_interval: usize,
+    ) -> bm13xx_asic::Result<heapless::Vec<CmdDelay, N>> {
+        check_addr_interval(chain_asic_num, asic_addr_interval)?;
+        let mut seq = heapless::Vec::new();
+        for asic_i in 0..chain_asic_num {
+            let chip_addr = chip_addr_checked(asic_i, asic_addr_interval, chain_asic_num)?;
+            seq.push(CmdDelay {
+                cmd: Command::write_reg(
+                    ChipNonceOffsetV2::ADDR,
+                    0x0000_0000,
+                    Destination::Chip(chip_addr),
+                ),
+                delay_ms: 0,
+            })
+            .map_err(|_| bm13xx_asic::Error::SequenceTooLong { capacity: N })?;
+        }
+        self.registers
+            .insert(ChipNonceOffsetV2::ADDR, 0x0000_0000)
+            .unwrap();
+        self.cno_interval = 0;
+        Ok(seq)
+    }
+
+    /// ## Check the Version Rolling register against the expected mask
+    ///
+    /// `version_mask` is `None` if Version Rolling is expected to be disabled.
+    ///
+    /// ### Errors
+    ///
+    /// - Missing register if the VersionRolling register is not in the map
+    /// - Version rolling mismatch if the register does not agree with `version_mask`
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{register::*, Asic, Error};
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// assert_eq!(bm1370.check_version_rolling(None), Ok(()));
+    /// while bm1370.set_version_rolling_next(0x1fff_e000).is_some() {}
+    /// assert_eq!(bm1370.check_version_rolling(Some(0x1fff_e000)), Ok(()));
+    /// // desync the register from the expected state
+    /// bm1370.registers.insert(VersionRolling::ADDR, 0x0000_ffff).unwrap();
+    /// assert_eq!(
+    ///     bm1370.check_version_rolling(Some(0x1fff_e000)),
+    ///     Err(Error::VersionRollingMismatch { expected: Some(0x1fff_e000), programmed: None })
+    /// );
+    /// ```
+    pub fn check_version_rolling(&self, version_mask: Option<u32>) -> bm13xx_asic::Result<()> {
+        let vers_roll = VersionRolling(*self.registers.get(&VersionRolling::ADDR).ok_or(
+            bm13xx_asic::Error::MissingRegister {
+                reg_addr: VersionRolling::ADDR,
+            },
+        )?);
+        let programmed = vers_roll.enabled().then_some(vers_roll.mask());
+        if programmed != version_mask {
+            return Err(bm13xx_asic::Error::VersionRollingMismatch {
+                expected: version_mask,
+                programmed,
+            });
+        }
+        Ok(())
+    }
+
+    /// ## Get the worst-case frame count of a chain init
+    ///
+    /// Number of frames sent by `init_next()`, `set_baudrate_next()` and
+    /// `split_nonce_between_chips_next()` for a chain of `asic_cnt` chips in `domain_cnt` domains.
+    /// The baudrate sequence is one frame longer when the target baudrate is above
+    /// `max_clki_baudrate()` (PLL3 path), this worst case is counted.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_CHAIN_INIT_CAPACITY};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// for (domain_cnt, asic_cnt) in [(1, 1), (13, 65), (13, 91), (32, 128)] {
+    ///     let domain_asic_cnt = asic_cnt / domain_cnt;
+    ///     let mut bm1370 = BM1370::default();
+    ///     let mut cnt = core::iter::from_fn(|| bm1370.init_next(256)).count();
+    ///     cnt += core::iter::from_fn(|| bm1370.set_baudrate_next(6_000_000, domain_cnt, domain_asic_cnt, 2)).count();
+    ///     cnt += core::iter::from_fn(|| bm1370.split_nonce_between_chips_next(asic_cnt, 2)).count();
+    ///     assert_eq!(BM1370::max_init_frames(domain_cnt, asic_cnt), cnt);
+    ///     // below max_clki_baudrate(input_clock_freq), the baudrate sequence is one frame shorter
+    ///     let mut bm1370 = BM1370::default();
+    ///     let cnt = core::iter::from_fn(|| bm1370.set_baudrate_next(1_000_000, domain_cnt, domain_asic_cnt, 2)).count();
+    ///     assert_eq!(cnt, 3 * domain_cnt + 3);
+    /// }
+    /// assert!(BM1370::max_init_frames(256, 256) <= BM1370_CHAIN_INIT_CAPACITY); // whole address space
+    /// ```
+    pub const fn max_init_frames(domain_cnt: usize, asic_cnt: usize) -> usize {
+        // init_next(): 4 frames
+        // set_baudrate_next(): start, 1 IoDriverStrenghtConfiguration and 2 UARTRelay per domain,
+        // PLL3Parameter, then FastUARTConfigurationV2 (and PLL3Parameter before it on the PLL3 path)
+        // split_nonce_between_chips_next(): 1 ChipNonceOffsetV2 per chip
+        4 + (3 * domain_cnt + 4) + asic_cnt
+    }
+
+    /// ## Get the number of Cores per Domain
+    ///
+    /// If `BM1370_CORE_CNT` does not split evenly between the `BM1370_DOMAIN_CNT` Domains,
+    /// the count is rounded up, giving the capacity of the largest Domain.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_CORE_CNT, BM1370_DOMAIN_CNT};
+    ///
+    /// let bm1370 = BM1370::default();
+    /// assert_eq!(bm1370.cores_per_domain(), 32);
+    /// assert_eq!(bm1370.cores_per_domain() * BM1370_DOMAIN_CNT, BM1370_CORE_CNT);
+    /// ```
+    pub fn cores_per_domain(&self) -> usize {
+        BM1370_CORE_CNT.div_ceil(BM1370_DOMAIN_CNT)
+    }
+
+    /// ## Get the number of enabled Small Cores per Domain
+    ///
+    /// Based on the `BM1370_SMALL_CORE_CNT` enabled Small Cores, not the 2048 of the Core layout.
+    /// If they do not split evenly between the `BM1370_DOMAIN_CNT` Domains,
+    /// the count is rounded up, giving the capacity of the largest Domain.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_DOMAIN_CNT, BM1370_SMALL_CORE_CNT};
+    ///
+    /// let bm1370 = BM1370::default();
+    /// assert_eq!(bm1370.small_cores_per_domain(), 510);
+    /// assert_eq!(bm1370.small_cores_per_domain() * BM1370_DOMAIN_CNT, BM1370_SMALL_CORE_CNT);
+    /// ```
+    pub fn small_cores_per_domain(&self) -> usize {
+        BM1370_SMALL_CORE_CNT.div_ceil(BM1370_DOMAIN_CNT)
+    }
+
+    /// PLL3 dividers used for the UART clock, as seen on captured sequences.
+    fn default_uart_pll() -> bm13xx_asic::pll::Pll {
+        *bm13xx_asic::pll::Pll::default()
+            .lock()
+            .enable()
+            .set_fb_div(112)
+            .set_ref_div(1)
+            .set_post1_div(1)
+            .set_post2_div(1)
+            .set_out_div(BM1370_PLL_OUT_UART, 6)
+    }
+
+    /// ## Configure the UART PLL
+    ///
+    /// Program the PLL3 dividers for a `target` UART clock on `BM1370_PLL_OUT_UART`, and keep
+    /// track of the PLL3Parameter register.
+    /// The default dividers (FBDIV 112, REFDIV 1, POSTDIV1 1, POSTDIV2 1, DIV4 6) are used if they
+    /// reach `target` exactly, otherwise the dividers are solved with `Pll::solve_for_freq()`.
+    /// If `target` cannot be reached exactly, the default dividers are kept.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_PLL_ID_UART, BM1370_PLL_OUT_UART};
+    /// use bm13xx_asic::register::*;
+    /// use fugit::HertzU64;
     ///
     /// let mut bm1370 = BM1370::default();
-    /// bm1370.set_chip_addr(2);
-    /// assert_eq!(bm1370.chip_addr, 2);
+    /// // UART clock for 6 Mbaud with BT8D=7: 2 * 6_000_000 * (7 + 1)
+    /// bm1370.configure_uart_pll(HertzU64::MHz(96));
+    /// assert_eq!(bm1370.plls[BM1370_PLL_ID_UART].frequency(bm1370.input_clock_freq, BM1370_PLL_OUT_UART), HertzU64::MHz(96));
+    /// assert_eq!(bm1370.registers.get(&PLL3Parameter::ADDR).unwrap(), &bm1370.plls[BM1370_PLL_ID_UART].parameter());
+    /// // unreachable target, default dividers are kept
+    /// bm1370.configure_uart_pll(HertzU64::Hz(96_000_001));
+    /// assert_eq!(bm1370.plls[BM1370_PLL_ID_UART].frequency(bm1370.input_clock_freq, BM1370_PLL_OUT_UART), HertzU64::MHz(100));
     /// ```
-    pub fn set_chip_addr(&mut self, chip_addr: u8) {
-        self.chip_addr = chip_addr;
-    }
-
-    pub fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
-        self.plls[BM1370_PLL_ID_HASH].set_frequency(
-            self.input_clock_freq,
-            BM1370_PLL_OUT_HASH,
-            freq,
-            false,
-        );
+    pub fn configure_uart_pll(&mut self, target: HertzU64) -> &mut Self {
+        let mut pll = Self::default_uart_pll();
+        if pll.frequency(self.input_clock_freq, BM1370_PLL_OUT_UART) != target {
+            if let Some(dividers) = bm13xx_asic::pll::Pll::solve_for_freq(
+                self.input_clock_freq,
+                target,
+                BM1370_PLL_OUT_UART,
+            ) {
+                pll.set_dividers(BM1370_PLL_OUT_UART, &dividers);
+            }
+        }
+        self.plls[BM1370_PLL_ID_UART] = pll;
+        self.registers
+            .insert(PLL3Parameter::ADDR, pll.parameter())
+            .unwrap();
         self
     }
 
@@ -129,200 +1204,232 @@ impl Default for BM1370 {
             input_clock_freq: HertzU64::MHz(25),
             plls: [bm13xx_asic::pll::Pll::default(); BM1370_PLL_CNT],
             chip_addr: 0,
-            registers: FnvIndexMap::<_, _, 64>::new(),
+            registers: FnvIndexMap::<_, _, BM1370_REGISTER_CAPACITY>::new(),
             core_registers: FnvIndexMap::<_, _, 16>::new(),
+            variant: None,
             cno_interval: 0,
+            idle_clk_ord_en: None,
         };
         bm1370.reset();
         bm1370
     }
 }
 
+/// ## Take a snapshot of a BM1370
+///
+/// ### Example
+/// ```
+/// use bm1370::BM1370;
+/// use bm13xx_asic::{register::*, Asic, ChipSnapshot};
+/// use fugit::HertzU64;
+///
+/// let mut bm1370 = BM1370::default();
+/// bm1370.set_chip_addr(4);
+/// bm1370.set_hash_freq(HertzU64::MHz(400));
+/// let snap = ChipSnapshot::from(&bm1370);
+/// assert_eq!(snap.chip_id, 0x1370);
+/// assert_eq!(snap.chip_addr, 4);
+/// assert_eq!(snap.hash_freq_hz, 400_000_000);
+/// assert_eq!(snap.version_rolling, None);
+/// bm1370.registers.insert(VersionRolling::ADDR, 0x9000_ffff).unwrap();
+/// assert_eq!(ChipSnapshot::from(&bm1370).version_rolling, Some(0x1fffe000));
+/// ```
+impl From<&BM1370> for ChipSnapshot {
+    fn from(bm: &BM1370) -> Self {
+        ChipSnapshot::from_asic(bm, bm.chip_addr, bm.version_mask())
+    }
+}
+
+/// # BM1370 Bring-up State
+///
+/// Track the last `BringUpStep` run on a BM1370 chain and give the command list of the next one,
+/// refusing to run the steps out of the `Asic::bring_up_order()`.
+/// The `VersionRolling` step has no command if `cfg.version_mask` is `None`.
+///
+/// ### Example
+/// ```
+/// use bm1370::{BringUpState, BM1370};
+/// use bm13xx_asic::{Asic, BringUpConfig, BringUpStep, Error};
+/// use fugit::HertzU64;
+///
+/// let cfg = BringUpConfig { difficulty: 256, hash_freq: HertzU64::MHz(75), version_mask: Some(0x1fff_e000) };
+/// let mut bm1370 = BM1370::default();
+/// let mut state = BringUpState::new(cfg, 1_000_000, 1, 1, 2);
+/// assert_eq!(state.phase(), None);
+/// // out of order steps are refused
+/// assert_eq!(
+///     state.enter::<64>(&mut bm1370, BringUpStep::HashFreq),
+///     Err(Error::BringUpOutOfOrder { current: None, requested: BringUpStep::HashFreq })
+/// );
+/// assert_eq!(state.phase(), None);
+/// // happy path
+/// assert!(state.next_commands::<64>(&mut bm1370).unwrap().is_empty());
+/// assert_eq!(state.phase(), Some(BringUpStep::Reset));
+/// assert!(!state.next_commands::<64>(&mut bm1370).unwrap().is_empty());
+/// assert_eq!(state.phase(), Some(BringUpStep::Init));
+/// assert_eq!(
+///     state.enter::<64>(&mut bm1370, BringUpStep::Init),
+///     Err(Error::BringUpOutOfOrder { current: Some(BringUpStep::Init), requested: BringUpStep::Init })
+/// );
+/// assert!(!state.next_commands::<64>(&mut bm1370).unwrap().is_empty());
+/// assert_eq!(state.phase(), Some(BringUpStep::ResetCore));
+/// // a step that does not fit leaves the chip and the state untouched, so it can be retried
+/// let freq = bm1370.hash_freq();
+/// assert_eq!(state.next_commands::<2>(&mut bm1370), Err(Error::SequenceTooLong { capacity: 2 }));
+/// assert_eq!(state.phase(), Some(BringUpStep::ResetCore));
+/// assert_eq!(bm1370.hash_freq(), freq);
+/// assert_eq!(state.next_commands::<64>(&mut bm1370).unwrap().len(), 5);
+/// assert_eq!(state.phase(), Some(BringUpStep::HashFreq));
+/// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(75));
+/// assert!(!state.next_commands::<64>(&mut bm1370).unwrap().is_empty());
+/// assert_eq!(state.phase(), Some(BringUpStep::Baudrate));
+/// assert!(!state.enter::<64>(&mut bm1370, BringUpStep::VersionRolling).unwrap().is_empty());
+/// assert_eq!(state.phase(), Some(BringUpStep::VersionRolling));
+/// assert_eq!(bm1370.version_mask(), Some(0x1fff_e000));
+/// assert_eq!(
+///     state.next_commands::<64>(&mut bm1370),
+///     Err(Error::BringUpOutOfOrder {
+///         current: Some(BringUpStep::VersionRolling),
+///         requested: BringUpStep::VersionRolling
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BringUpState {
+    phase: Option<BringUpStep>,
+    pub cfg: BringUpConfig,
+    pub baudrate: u32,
+    pub chain_domain_cnt: usize,
+    pub domain_asic_cnt: usize,
+    pub asic_addr_interval: usize,
+}
+
+impl BringUpState {
+    pub fn new(
+        cfg: BringUpConfig,
+        baudrate: u32,
+        chain_domain_cnt: usize,
+        domain_asic_cnt: usize,
+        asic_addr_interval: usize,
+    ) -> Self {
+        BringUpState {
+            phase: None,
+            cfg,
+            baudrate,
+            chain_domain_cnt,
+            domain_asic_cnt,
+            asic_addr_interval,
+        }
+    }
+
+    /// Last step run, `None` if the bring-up did not start yet.
+    pub const fn phase(&self) -> Option<BringUpStep> {
+        self.phase
+    }
+
+    /// Step following the last one run in the `bm1370.bring_up_order()`, `None` once all are run.
+    fn next_step(&self, bm1370: &BM1370) -> Option<BringUpStep> {
+        let order = bm1370.bring_up_order();
+        match self.phase {
+            None => order.first().copied(),
+            Some(phase) => order
+                .iter()
+                .skip_while(|&&step| step != phase)
+                .nth(1)
+                .copied(),
+        }
+    }
+
+    /// ## Get the command list of the next step
+    ///
+    /// See `enter()`, with the step following the last one run.
+    pub fn next_commands<const N: usize>(
+        &mut self,
+        bm1370: &mut BM1370,
+    ) -> bm13xx_asic::Result<heapless::Vec<CmdDelay, N>> {
+        let step = self
+            .next_step(bm1370)
+            .or(self.phase)
+            .unwrap_or(BringUpStep::Reset);
+        self.enter(bm1370, step)
+    }
+
+    /// ## Run a step and get its command list
+    ///
+    /// The command list is built against a clone of `bm1370` which is only committed on success,
+    /// so after an error both the chip and the state are left untouched and the step can be retried.
+    ///
+    /// ### Errors
+    ///
+    /// - Bring-up out of order if `step` does not follow the last one run in `bm1370.bring_up_order()`
+    /// - Sequence too long if the step command list does not fit in `N`
+    pub fn enter<const N: usize>(
+        &mut self,
+        bm1370: &mut BM1370,
+        step: BringUpStep,
+    ) -> bm13xx_asic::Result<heapless::Vec<CmdDelay, N>> {
+        if self.next_step(bm1370) != Some(step) {
+            return Err(bm13xx_asic::Error::BringUpOutOfOrder {
+                current: self.phase,
+                requested: step,
+            });
+        }
+        let mut asic = bm1370.clone();
+        let mut seq = heapless::Vec::new();
+        let mut push = |cmd: CmdDelay| {
+            seq.push(cmd)
+                .map_err(|_| bm13xx_asic::Error::SequenceTooLong { capacity: N })
+        };
+        match step {
+            BringUpStep::Reset => asic.reset(),
+            BringUpStep::Init => {
+                while let Some(cmd) = asic.init_next(self.cfg.difficulty) {
+                    push(cmd)?;
+                }
+            }
+            BringUpStep::ResetCore => {
+                while let Some(cmd) = asic.reset_core_next(Destination::All) {
+                    push(cmd)?;
+                }
+            }
+            BringUpStep::HashFreq => {
+                while let Some(cmd) = asic.set_hash_freq_next(self.cfg.hash_freq) {
+                    push(cmd)?;
+                }
+            }
+            BringUpStep::Baudrate => {
+                while let Some(cmd) = asic.set_baudrate_next(
+                    self.baudrate,
+                    self.chain_domain_cnt,
+                    self.domain_asic_cnt,
+                    self.asic_addr_interval,
+                ) {
+                    push(cmd)?;
+                }
+            }
+            BringUpStep::VersionRolling => {
+                if let Some(mask) = self.cfg.version_mask {
+                    while let Some(cmd) = asic.set_version_rolling_next(mask) {
+                        push(cmd)?;
+                    }
+                }
+            }
+        }
+        *bm1370 = asic;
+        self.phase = Some(step);
+        Ok(seq)
+    }
+}
+
 impl Asic for BM1370 {
     /// ## Reset the Chip to default state
+    ///
+    /// The chip power-on defaults are reloaded, including the `cno_interval` programmed by the
+    /// split nonce sequence. The `input_clock_freq` and `variant` describe the board the chip is
+    /// soldered on, which a chip reset does not change.
     fn reset(&mut self) {
-        self.seq_step = SequenceStep::default();
-        self.sha = bm13xx_asic::sha::Sha::default();
-        self.input_clock_freq = HertzU64::MHz(25);
-        self.plls = [bm13xx_asic::pll::Pll::default(); BM1370_PLL_CNT];
-        self.chip_addr = 0;
-        self.registers = FnvIndexMap::<_, _, 64>::new();
-        self.core_registers = FnvIndexMap::<_, _, 16>::new();
-        // Default PLLs Parameter
-        self.plls[0].set_parameter(0xC054_0165);
-        self.plls[1].set_parameter(0x2050_0174);
-        self.plls[2].set_parameter(0x2050_0174);
-        self.plls[3].set_parameter(0x0000_0000);
-        // Default PLLs Divider
-        self.plls[0].set_divider(0x0000_0000);
-        self.plls[1].set_divider(0x0000_0000);
-        self.plls[2].set_divider(0x0000_0000);
-        self.plls[3].set_divider(0x0000_0000);
-        // Default Registers Value
-        self.registers
-            .insert(ChipIdentification::ADDR, 0x1370_0000)
-            .unwrap();
-        self.registers.insert(HashRate::ADDR, 0x0000_0000).unwrap();
-        self.registers
-            .insert(PLL0Parameter::ADDR, 0xc054_0165)
-            .unwrap();
-        self.registers
-            .insert(ChipNonceOffsetV2::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(HashCountingNumber::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(TicketMask::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(MiscControl::ADDR, 0x0000_c100)
-            .unwrap();
-        self.registers
-            .insert(I2CControl::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(OrderedClockEnable::ADDR, 0x0000_0007)
-            .unwrap();
-        self.registers.insert(Reg24::ADDR, 0x0010_0000).unwrap();
-        self.registers
-            .insert(FastUARTConfigurationV2::ADDR, 0x0130_1a00)
-            .unwrap();
-        self.registers.insert(UARTRelay::ADDR, 0x000f_0000).unwrap();
-        self.registers.insert(Reg30::ADDR, 0x0000_0080).unwrap();
-        self.registers.insert(Reg34::ADDR, 0x0000_0000).unwrap();
-        self.registers
-            .insert(TicketMask2::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(CoreRegisterControl::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(CoreRegisterValue::ADDR, 0x0802_0100)
-            .unwrap();
-        self.registers
-            .insert(ExternalTemperatureSensorRead::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers.insert(ErrorFlag::ADDR, 0x0000_0000).unwrap();
-        self.registers
-            .insert(NonceErrorCounter::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(NonceOverflowCounter::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(AnalogMuxControlV2::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(IoDriverStrenghtConfiguration::ADDR, 0x0001_2111)
-            .unwrap();
-        self.registers.insert(TimeOut::ADDR, 0x0000_FFFF).unwrap();
-        self.registers
-            .insert(PLL1Parameter::ADDR, 0x2050_0174)
-            .unwrap();
-        self.registers
-            .insert(PLL2Parameter::ADDR, 0x2050_0174)
-            .unwrap();
-        self.registers
-            .insert(PLL3Parameter::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(OrderedClockMonitor::ADDR, 0x0001_0200)
-            .unwrap();
-        self.registers
-            .insert(PLL0Divider::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(PLL1Divider::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(PLL2Divider::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(PLL3Divider::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(ClockOrderControl0::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(ClockOrderControl1::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(ClockOrderStatus::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(FrequencySweepControl1::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(GoldenNonceForSweepReturn::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(ReturnedGroupPatternStatus::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(NonceReturnedTimeout::ADDR, 0x00f7_0073)
-            .unwrap();
-        self.registers
-            .insert(ReturnedSinglePatternStatus::ADDR, 0x0000_0000)
-            .unwrap();
-        self.registers
-            .insert(VersionRolling::ADDR, 0x0000_ffff)
-            .unwrap();
-
-        self.registers.insert(RegA8::ADDR, 0x0007_0000).unwrap();
-        self.registers.insert(RegAC::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegB0::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegB4::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegB8::ADDR, 0x2000_0000).unwrap();
-        self.registers.insert(RegBC::ADDR, 0x0000_3313).unwrap();
-        self.registers.insert(RegC0::ADDR, 0x0000_2000).unwrap();
-        self.registers.insert(RegC4::ADDR, 0x0000_b850).unwrap();
-        self.registers.insert(RegC8::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegCC::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegD0::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegD4::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegD8::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegDC::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegE0::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegE4::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegE8::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegEC::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegF0::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegF4::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegF8::ADDR, 0x0000_0000).unwrap();
-        self.registers.insert(RegFC::ADDR, 0x0000_0000).unwrap();
-        // Default Core Registers Value
-        self.core_registers
-            .insert(ClockDelayCtrlV2::ID, 0x52)
-            .unwrap();
-        // self.core_registers.insert(1, 0x00).unwrap(); // not used anywhere in official FW
-        self.core_registers.insert(CoreReg2::ID, 0x55).unwrap();
-        // self.core_registers.insert(CoreError::ID, 0x00).unwrap();
-        // self.core_registers.insert(CoreEnable::ID, 0x08).unwrap();
-        self.core_registers.insert(HashClockCtrl::ID, 0x00).unwrap();
-        self.core_registers
-            .insert(HashClockCounter::ID, 0x08)
-            .unwrap();
-        // self
-        //     .core_registers
-        //     .insert(SweepClockCtrl::ID, 0x00)
-        //     .unwrap();
-        self.core_registers.insert(CoreReg8::ID, 0x00).unwrap();
-        // self.core_registers.insert(9, 0x00).unwrap();
-        // self.core_registers.insert(9, 0x00).unwrap();
-        self.core_registers.insert(CoreReg11::ID, 0x00).unwrap();
-        // self.core_registers.insert(12, 0x00).unwrap();
-        // self.core_registers.insert(13, 0x15).unwrap();
-        // self.core_registers.insert(14, 0x00).unwrap();
-        // self.core_registers.insert(15, 0x00).unwrap();
-        // self.core_registers.insert(16, 0xbe).unwrap();
-        // self.core_registers.insert(17, 0x00).unwrap();
-        // self.core_registers.insert(18, 0x00).unwrap();
-        // self.core_registers.insert(19, 0x00).unwrap();
-        // self.core_registers.insert(20, 0x00).unwrap();
-        // self.core_registers.insert(21, 0x00).unwrap();
-        self.core_registers.insert(CoreReg22::ID, 0x00).unwrap();
-        // self.core_registers.insert(23, 0x80).unwrap();
+        self.reset_chip_state();
     }
 
     /// ## Get the Chip ID
@@ -402,27 +1509,89 @@ impl Asic for BM1370 {
     /// assert_eq!(bm1370.set_hash_freq(HertzU64::MHz(200)).hash_freq(), HertzU64::MHz(200));
     /// ```
     fn hash_freq(&self) -> HertzU64 {
-        self.plls[BM1370_PLL_ID_HASH].frequency(self.input_clock_freq, BM1370_PLL_OUT_HASH)
+        pll_frequency(
+            &self.plls,
+            self.input_clock_freq,
+            BM1370_PLL_ID_HASH,
+            BM1370_PLL_OUT_HASH,
+        )
+    }
+
+    /// ## Set the Hash Frequency
+    ///
+    /// The PLL dividers are solved against the board `input_clock_freq`.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::{BM1370, BM1370_PLL_ID_HASH, BM1370_PLL_OUT_HASH};
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1370 = BM1370::new_with_clk(HertzU64::MHz(20));
+    /// bm1370.set_hash_freq(HertzU64::MHz(400));
+    /// let pll = bm1370.plls[BM1370_PLL_ID_HASH];
+    /// assert_eq!((pll.fb_div(), pll.ref_div(), pll.post1_div(), pll.post2_div()), (240, 2, 5, 0));
+    /// assert_eq!(pll.out_div(BM1370_PLL_OUT_HASH), 0);
+    /// assert_eq!(pll.parameter(), 0xD0F0_0250);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(400));
+    /// bm1370.reset(); // the board clock is kept
+    /// assert_eq!(bm1370.input_clock_freq, HertzU64::MHz(20));
+    /// ```
+    fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
+        self.plls[BM1370_PLL_ID_HASH].set_frequency(
+            self.input_clock_freq,
+            BM1370_PLL_OUT_HASH,
+            freq,
+            false,
+        );
+        self
     }
 
     /// ## Init the Chip command list
     ///
     /// ### Example
     /// ```
-    /// use bm1370::BM1370;
-    /// use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay};
+    /// use bm1370::{BoardVariant, BM1370};
+    /// use bm13xx_asic::{core_register::*, register::*, test_util::{frames_equal, hex_dump}, Asic, CmdDelay};
+    /// use heapless::Vec;
     ///
     /// let mut bm1370 = BM1370::default();
     /// // Seen on S21XP
-    /// assert_eq!(bm1370.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8B, 0x00, 0x12], delay_ms: 10}));
-    /// assert_eq!(bm1370.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x10, 0x12], delay_ms: 10}));
-    /// assert_eq!(bm1370.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08], delay_ms: 10}));
-    /// assert_eq!(bm1370.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x54, 0x00, 0x00, 0x00, 0x03, 0x1d], delay_ms: 0}));
-    /// assert_eq!(bm1370.init_next(256), None);
+    /// let mut seq = Vec::<CmdDelay, 8>::new();
+    /// while let Some(step) = bm1370.init_next(256) {
+    ///     seq.push(step).unwrap();
+    /// }
+    /// assert_eq!(
+    ///     hex_dump::<256>(&seq),
+    ///     "55 AA 51 09 00 3C 80 00 8B 00 12 +10ms\n\
+    ///      55 AA 51 09 00 3C 80 00 80 10 12 +10ms\n\
+    ///      55 AA 51 09 00 14 00 00 00 FF 08 +10ms\n\
+    ///      55 AA 51 09 00 54 00 00 00 03 1D +0ms\n"
+    /// );
     /// assert_eq!(bm1370.core_registers.get(&CoreReg11::ID).unwrap(), &0x00);
     /// assert_eq!(bm1370.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(), &0x10);
     /// assert_eq!(bm1370.registers.get(&TicketMask::ADDR).unwrap(), &0x0000_00ff);
     /// assert_eq!(bm1370.registers.get(&AnalogMuxControlV2::ADDR).unwrap(), &0x0000_0003);
+    /// // Seen on S21Pro
+    /// let mut bm1370 = BM1370::default();
+    /// bm1370.variant = Some(BoardVariant::S21Pro);
+    /// let seq: Vec<CmdDelay, 8> = bm1370.init_frames(256).collect();
+    /// let golden: [&[u8]; 4] = [
+    ///     &[0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8B, 0x00, 0x12],
+    ///     &[0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x0c, 0x11],
+    ///     &[0x55, 0xaa, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08],
+    ///     &[0x55, 0xaa, 0x51, 0x09, 0x00, 0x54, 0x00, 0x00, 0x00, 0x03, 0x1d],
+    /// ];
+    /// assert_eq!(frames_equal(&seq, &golden), Ok(()));
+    /// assert_eq!(bm1370.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(), &0x0c);
+    /// // Preview does not touch the chip state
+    /// let bm1370 = BM1370::default();
+    /// let preview = bm1370.preview_init::<8>(256).unwrap();
+    /// assert_eq!(preview.len(), 4);
+    /// // Or stream the commands one at a time
+    /// assert_eq!(BM1370::default().init_frames(256).count(), preview.len());
+    /// assert_eq!(preview[3], CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x54, 0x00, 0x00, 0x00, 0x03, 0x1d], delay_ms: 0});
+    /// assert_eq!(bm1370.registers.get(&TicketMask::ADDR).unwrap(), &0x0000_0000);
     /// ```
     fn init_next(&mut self, difficulty: u32) -> Option<CmdDelay> {
         match self.seq_step {
@@ -432,27 +1601,7 @@ impl Asic for BM1370 {
                         self.seq_step = SequenceStep::Init(1);
                         // 2 - [55, AA, 51, 09, 00, 3C, 80, 00, 80, 0C, 11] // S21 Pro //TODO: understand why S21Pro has a different value (0x0c)
                         // 2 - [55, AA, 51, 09, 00, 3C, 80, 00, 80, 10, 12] // S21 XP
-                        // Seems to be a ClockDelayCtrlV3 ? because 0x0c has a 1 in bit2 which is not in ClockDelayCtrlV2
-                        // S21Pro
-                        // let clk_dly_ctrl = 0x0c;
-                        // let clk_dly_ctrl = ClockDelayCtrlV2(
-                        //     *self.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(),
-                        // )
-                        // .set_ccdly(0)
-                        // .set_pwth(0)
-                        // .enable_bit2()
-                        // .disable_sweep_frequency_mode()
-                        // .val();
-                        // S21XP
-                        // let clk_dly_ctrl = 0x10;
-                        let clk_dly_ctrl = ClockDelayCtrlV2(
-                            *self.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(),
-                        )
-                        .set_ccdly(0)
-                        .set_pwth(2)
-                        // .disable_bit2()
-                        .disable_sweep_frequency_mode()
-                        .val();
+                        let clk_dly_ctrl = self.clock_delay_ctrl();
                         self.core_registers
                             .insert(ClockDelayCtrlV2::ID, clk_dly_ctrl)
                             .unwrap();
@@ -525,8 +1674,9 @@ impl Asic for BM1370 {
     ///
     /// ### Example
     /// ```
-    /// use bm1370::{BM1370, BM1370_PLL_ID_UART};
-    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use bm1370::{BM1370, BM1370_PLL_ID_UART, BM1370_PLL_OUT_UART};
+    /// use bm13xx_asic::{register::*, test_util::hex_dump, Asic, CmdDelay};
+    /// use fugit::HertzU64;
     ///
     /// let mut bm1370 = BM1370::default();
     /// // real example from S21XP
@@ -545,7 +1695,7 @@ impl Asic for BM1370 {
     /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x1a, 0x58, 0x00, 0x01, 0x31, 0x11, 0x09], delay_ms: 0}));
     /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x0c, 0x58, 0x00, 0x01, 0x31, 0x11, 0x0e], delay_ms: 0}));
     // assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x68, 0x5a, 0xa5, 0x5a, 0xa5, 0x1c], delay_ms: 0})); // real values
-    /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x68, 0x5a, 0xa5, 26, 37, 20], delay_ms: 0}));
+    /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x68, 0xc0, 0x70, 0x01, 0x11, 0x00], delay_ms: 0}));
     /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xa8, 0x2C, 0x00, 0x15, 0x00, 0x03, 0x14], delay_ms: 0}));
     /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xb4, 0x2C, 0x00, 0x15, 0x00, 0x03, 0x1f], delay_ms: 0}));
     /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 7, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x9a, 0x2C, 0x00, 0x1c, 0x00, 0x03, 0x08], delay_ms: 0}));
@@ -579,6 +1729,63 @@ impl Asic for BM1370 {
     // assert_eq!(bm1370.registers.get(&PLL3Parameter::ADDR).unwrap(), &0x5aa5_5aa5); // real value
     /// assert_eq!(bm1370.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(), &0x0130_0000);
     ///
+    /// // 13 domains of 10 chips every 2 addresses do not fit in the 8 bits address space
+    /// assert_eq!(bm1370.set_baudrate_next(3_125_000, 13, 10, 2), None);
+    ///
+    /// // 6 Mbaud cannot be reached from the default 100 MHz UART clock, other dividers are used
+    /// let mut bm1370 = BM1370::default();
+    /// while bm1370.set_baudrate_next(6_000_000, 1, 1, 2).is_some() {}
+    /// assert_eq!(bm1370.plls[BM1370_PLL_ID_UART].frequency(bm1370.input_clock_freq, BM1370_PLL_OUT_UART), HertzU64::MHz(96));
+    /// assert_eq!(bm1370.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(), &0x0540_0700);
+    ///
+    /// // Whole S21XP sequence: IoDriverStrenghtConfiguration, PLL3Parameter, UARTRelay and FastUARTConfigurationV2.
+    /// // The captured trace writes 0x5aa5_5aa5 in PLL3Parameter, the default UART PLL dividers are written instead.
+    /// let seq = BM1370::default().preview_baudrate::<64>(3_125_000, 13, 7, 2).unwrap();
+    /// assert_eq!(
+    ///     hex_dump::<2048>(&seq),
+    ///     "55 AA 51 09 00 58 00 01 11 11 0D +0ms\n\
+    ///      55 AA 41 09 B4 58 00 01 31 11 00 +0ms\n\
+    ///      55 AA 41 09 A6 58 00 01 31 11 1C +0ms\n\
+    ///      55 AA 41 09 98 58 00 01 31 11 0E +0ms\n\
+    ///      55 AA 41 09 8A 58 00 01 31 11 12 +0ms\n\
+    ///      55 AA 41 09 7C 58 00 01 31 11 07 +0ms\n\
+    ///      55 AA 41 09 6E 58 00 01 31 11 1B +0ms\n\
+    ///      55 AA 41 09 60 58 00 01 31 11 0C +0ms\n\
+    ///      55 AA 41 09 52 58 00 01 31 11 16 +0ms\n\
+    ///      55 AA 41 09 44 58 00 01 31 11 11 +0ms\n\
+    ///      55 AA 41 09 36 58 00 01 31 11 07 +0ms\n\
+    ///      55 AA 41 09 28 58 00 01 31 11 13 +0ms\n\
+    ///      55 AA 41 09 1A 58 00 01 31 11 09 +0ms\n\
+    ///      55 AA 41 09 0C 58 00 01 31 11 0E +0ms\n\
+    ///      55 AA 51 09 00 68 C0 70 01 11 00 +0ms\n\
+    ///      55 AA 41 09 A8 2C 00 15 00 03 14 +0ms\n\
+    ///      55 AA 41 09 B4 2C 00 15 00 03 1F +0ms\n\
+    ///      55 AA 41 09 9A 2C 00 1C 00 03 08 +0ms\n\
+    ///      55 AA 41 09 A6 2C 00 1C 00 03 05 +0ms\n\
+    ///      55 AA 41 09 8C 2C 00 23 00 03 1D +0ms\n\
+    ///      55 AA 41 09 98 2C 00 23 00 03 05 +0ms\n\
+    ///      55 AA 41 09 7E 2C 00 2A 00 03 15 +0ms\n\
+    ///      55 AA 41 09 8A 2C 00 2A 00 03 1F +0ms\n\
+    ///      55 AA 41 09 70 2C 00 31 00 03 08 +0ms\n\
+    ///      55 AA 41 09 7C 2C 00 31 00 03 00 +0ms\n\
+    ///      55 AA 41 09 62 2C 00 38 00 03 12 +0ms\n\
+    ///      55 AA 41 09 6E 2C 00 38 00 03 1A +0ms\n\
+    ///      55 AA 41 09 54 2C 00 3F 00 03 11 +0ms\n\
+    ///      55 AA 41 09 60 2C 00 3F 00 03 0F +0ms\n\
+    ///      55 AA 41 09 46 2C 00 46 00 03 0E +0ms\n\
+    ///      55 AA 41 09 52 2C 00 46 00 03 16 +0ms\n\
+    ///      55 AA 41 09 38 2C 00 4D 00 03 03 +0ms\n\
+    ///      55 AA 41 09 44 2C 00 4D 00 03 02 +0ms\n\
+    ///      55 AA 41 09 2A 2C 00 54 00 03 00 +0ms\n\
+    ///      55 AA 41 09 36 2C 00 54 00 03 0B +0ms\n\
+    ///      55 AA 41 09 1C 2C 00 5B 00 03 1D +0ms\n\
+    ///      55 AA 41 09 28 2C 00 5B 00 03 03 +0ms\n\
+    ///      55 AA 41 09 0E 2C 00 62 00 03 09 +0ms\n\
+    ///      55 AA 41 09 1A 2C 00 62 00 03 11 +0ms\n\
+    ///      55 AA 41 09 00 2C 00 69 00 03 0D +0ms\n\
+    ///      55 AA 41 09 0C 2C 00 69 00 03 05 +200ms\n\
+    ///      55 AA 51 09 00 28 01 30 00 00 1A +200ms\n"
+    /// );
     /// ```
     fn set_baudrate_next(
         &mut self,
@@ -594,7 +1801,6 @@ impl Asic for BM1370 {
         let sub_seq5_start = sub_seq4_start + chain_domain_cnt;
         let sub_seq6_start = sub_seq5_start + 1;
         let end = sub_seq6_start + 1;
-        let pll3_div4 = 6;
         match self.seq_step {
             SequenceStep::Baudrate(step) => {
                 if (sub_seq1_start..sub_seq2_start).contains(&step) {
@@ -615,23 +1821,20 @@ impl Asic for BM1370 {
                         cmd: Command::write_reg(
                             IoDriverStrenghtConfiguration::ADDR,
                             io_drv_st_cfg,
-                            Destination::Chip(
-                                (((dom + 1) * domain_asic_cnt - 1) * asic_addr_interval) as u8,
-                            ),
+                            Destination::Chip(chip_address(
+                                (dom + 1) * domain_asic_cnt - 1,
+                                asic_addr_interval,
+                            )?),
                         ),
                         delay_ms: 0,
                     })
                 } else if step == sub_seq2_start {
                     self.seq_step = SequenceStep::Baudrate(sub_seq3_start);
-                    self.plls[BM1370_PLL_ID_UART]
-                        .set_parameter(0x5aa5_5aa5) // TODO: replace these fixed values with equivalent individual ones below
-                        // .lock()
-                        // .enable()
-                        // .set_fb_div(112)
-                        // .set_ref_div(1)
-                        // .set_post1_div(1)
-                        // .set_post2_div(1)
-                        .set_out_div(BM1370_PLL_OUT_UART, pll3_div4);
+                    // captured sequences write 0x5aa5_5aa5 here, program the default dividers instead
+                    self.configure_uart_pll(
+                        Self::default_uart_pll()
+                            .frequency(self.input_clock_freq, BM1370_PLL_OUT_UART),
+                    );
                     let pll3_param = self.plls[BM1370_PLL_ID_UART].parameter();
                     self.registers
                         .insert(PLL3Parameter::ADDR, pll3_param)
@@ -661,7 +1864,10 @@ impl Asic for BM1370 {
                         cmd: Command::write_reg(
                             UARTRelay::ADDR,
                             uart_delay,
-                            Destination::Chip((dom * domain_asic_cnt * asic_addr_interval) as u8),
+                            Destination::Chip(chip_address(
+                                dom * domain_asic_cnt,
+                                asic_addr_interval,
+                            )?),
                         ),
                         delay_ms: 0,
                     })
@@ -687,14 +1893,15 @@ impl Asic for BM1370 {
                         cmd: Command::write_reg(
                             UARTRelay::ADDR,
                             uart_delay,
-                            Destination::Chip(
-                                (((dom + 1) * domain_asic_cnt - 1) * asic_addr_interval) as u8,
-                            ),
+                            Destination::Chip(chip_address(
+                                (dom + 1) * domain_asic_cnt - 1,
+                                asic_addr_interval,
+                            )?),
                         ),
                         delay_ms: if step == sub_seq5_start - 1 { 200 } else { 0 },
                     })
                 } else if step == sub_seq5_start {
-                    if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+                    if baudrate <= max_clki_baudrate(self.input_clock_freq) {
                         self.seq_step = SequenceStep::Baudrate(end);
                         let fbase = self.input_clock_freq.raw() as u32;
                         let bt8d = (fbase / (8 * baudrate)) - 1;
@@ -719,19 +1926,13 @@ impl Asic for BM1370 {
                         })
                     } else {
                         self.seq_step = SequenceStep::Baudrate(sub_seq6_start);
-                        self.plls[BM1370_PLL_ID_UART]
-                            // .set_parameter(0xC070_0111)
-                            .lock()
-                            .enable()
-                            .set_fb_div(112)
-                            .set_ref_div(1)
-                            .set_post1_div(1)
-                            .set_post2_div(1)
-                            .set_out_div(BM1370_PLL_OUT_UART, pll3_div4);
+                        let fbase = Self::default_uart_pll()
+                            .frequency(self.input_clock_freq, BM1370_PLL_OUT_UART)
+                            .raw();
+                        // default dividers cannot reach this baudrate exactly, look for other ones
+                        let bt8d = (fbase / (2 * baudrate as u64)).max(1) - 1;
+                        self.configure_uart_pll(HertzU64::Hz(2 * baudrate as u64 * (bt8d + 1)));
                         let pll3_param = self.plls[BM1370_PLL_ID_UART].parameter();
-                        self.registers
-                            .insert(PLL3Parameter::ADDR, pll3_param)
-                            .unwrap();
                         Some(CmdDelay {
                             cmd: Command::write_reg(
                                 PLL3Parameter::ADDR,
@@ -743,7 +1944,7 @@ impl Asic for BM1370 {
                     }
                 } else if step == sub_seq6_start {
                     self.seq_step = SequenceStep::Baudrate(end);
-                    if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+                    if baudrate <= max_clki_baudrate(self.input_clock_freq) {
                         // should not be reached for 2 reasons:
                         // - in step above we jump directly to end
                         // - after setting the chip's FastUartConfiguration with bclk_sel(BaudrateClockSelectV2::Clki) in previous step
@@ -763,14 +1964,18 @@ impl Asic for BM1370 {
                             delay_ms: 0,
                         })
                     } else {
-                        let fbase = self.plls[BM1370_PLL_ID_UART]
-                            .frequency(self.input_clock_freq, BM1370_PLL_OUT_UART)
-                            .raw();
+                        let fbase = pll_frequency(
+                            &self.plls,
+                            self.input_clock_freq,
+                            BM1370_PLL_ID_UART,
+                            BM1370_PLL_OUT_UART,
+                        )
+                        .raw();
                         let bt8d = (fbase as u32 / (2 * baudrate)) - 1;
                         let fast_uart_cfg = FastUARTConfigurationV2(
                             *self.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(),
                         )
-                        .set_pll1_div4(pll3_div4) // TODO: not sure yet where the pll3_div4 really fit into FastUartConfiguration
+                        .set_pll1_div4(self.plls[BM1370_PLL_ID_UART].out_div(BM1370_PLL_OUT_UART)) // TODO: not sure yet where the pll3_div4 really fit into FastUartConfiguration
                         .set_bclk_sel(BaudrateClockSelectV2::Pll1) // TODO: it should be Pll3, but not sure about the BCLK_SEL field yet for it
                         .set_bt8d(bt8d as u8)
                         .val();
@@ -794,6 +1999,8 @@ impl Asic for BM1370 {
                 }
             }
             _ => {
+                // do not start a sequence addressing chips out of the 8 bits address space
+                check_addr_interval(chain_domain_cnt * domain_asic_cnt, asic_addr_interval).ok()?;
                 // authorize a SetBaudrate sequence start whatever the current step was
                 self.seq_step = SequenceStep::Baudrate(sub_seq1_start);
                 let io_drv_st_cfg = IoDriverStrenghtConfiguration(
@@ -832,7 +2039,7 @@ impl Asic for BM1370 {
     ///
     /// ### Example
     /// ```
-    /// use bm1370::BM1370;
+    /// use bm1370::{BoardVariant, BM1370};
     /// use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay};
     /// use bm13xx_protocol::command::Destination;
     ///
@@ -844,10 +2051,16 @@ impl Asic for BM1370 {
     /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0xa8, 0x00, 0x07, 0x01, 0xf0, 0x15], delay_ms: 10}));
     /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x18, 0xf0, 0x00, 0xc1, 0x00, 0x0c], delay_ms: 10}));
     /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8B, 0x00, 0x1a], delay_ms: 10}));
-    // assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x0c, 0x19], delay_ms: 10})); // S21Pro
     /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x10, 0x1a], delay_ms: 10})); // S21XP
     /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x82, 0xaa, 0x05], delay_ms: 10}));
     /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), None);
+    /// let mut bm1370 = BM1370::default();
+    /// bm1370.variant = Some(BoardVariant::S21Pro);
+    /// bm1370.reset_core_next(Destination::Chip(0));
+    /// bm1370.reset_core_next(Destination::Chip(0));
+    /// bm1370.reset_core_next(Destination::Chip(0));
+    /// assert_eq!(bm1370.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x0c, 0x19], delay_ms: 10})); // S21Pro
+    /// assert_eq!(bm1370.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(), &0x0c);
     /// ```
     fn reset_core_next(&mut self, dest: Destination) -> Option<CmdDelay> {
         if dest == Destination::All {
@@ -928,18 +2141,7 @@ impl Asic for BM1370 {
                     }
                     2 => {
                         self.seq_step = SequenceStep::ResetCore(3);
-                        // Seemes to be a ClockDelayCtrlV3 ? because 0x0c has a 1 in bit2 which is not in ClockDelayCtrlV2
-                        // let clk_dly_ctrl = 0x0c; // S21Pro
-                        let clk_dly_ctrl = 0x10; // S21XP
-
-                        // let clk_dly_ctrl = ClockDelayCtrlV2( // TODO: replace the fixed value above with this detailed
-                        //         *self.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(),
-                        // )
-                        // .set_ccdly(0)
-                        // .set_pwth(0)
-                        // .enable_bit2()
-                        // .disable_sweep_frequency_mode()
-                        // .val();
+                        let clk_dly_ctrl = self.clock_delay_ctrl();
                         self.core_registers
                             .insert(ClockDelayCtrlV2::ID, clk_dly_ctrl)
                             .unwrap();
@@ -979,8 +2181,8 @@ impl Asic for BM1370 {
                     self.seq_step = SequenceStep::ResetCore(0);
                     let reg_a8 = RegA8(*self.registers.get(&RegA8::ADDR).unwrap())
                         .clr_b10()
-                        .set_b8()
-                        .set_b7_4(0xf)
+                        .enable_core_reset()
+                        .set_core_reset_mask(0xf)
                         .set_b3_0(0)
                         .val();
                     self.registers.insert(RegA8::ADDR, reg_a8).unwrap();
@@ -995,9 +2197,12 @@ impl Asic for BM1370 {
 
     /// ## Send Hash Frequency command list
     ///
+    /// The frequency is ramped by 6.25 MHz steps, each one rewriting PLL0Parameter, which briefly
+    /// unlocks the PLL and drops hashing.
+    ///
     /// ### Example
     /// ```
-    /// use bm1370::{BM1370, BM1370_PLL_ID_HASH};
+    /// use bm1370::{BM1370, BM1370_PLL_ID_HASH, BM1370_PLL_OUT_HASH};
     /// use bm13xx_asic::{register::*, Asic, CmdDelay};
     /// use fugit::HertzU64;
     ///
@@ -1011,6 +2216,13 @@ impl Asic for BM1370 {
     /// assert_eq!(bm1370.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xa8, 0x02, 0x63, 0x14], delay_ms: 400}));
     /// assert_eq!(bm1370.set_hash_freq_next(HertzU64::MHz(75)), None);
     /// assert_eq!(bm1370.plls[BM1370_PLL_ID_HASH].parameter(), 0xc0a8_0263);
+    /// // A previous configuration left a non null Hash output divider: it is reset before the ramp
+    /// let mut bm1370 = BM1370::default();
+    /// bm1370.plls[BM1370_PLL_ID_HASH].set_out_div(BM1370_PLL_OUT_HASH, 3);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::kHz(12_500));
+    /// assert_eq!(bm1370.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x00, 0x00, 0x00, 0x00, 24], delay_ms: 2}));
+    /// assert_eq!(bm1370.registers.get(&PLL0Divider::ADDR).unwrap(), &0x0000_0000);
+    /// assert_eq!(bm1370.hash_freq(), HertzU64::MHz(50));
     /// ```
     fn set_hash_freq_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
         match self.seq_step {
@@ -1044,6 +2256,8 @@ impl Asic for BM1370 {
             _ => {
                 // authorize a SetHashFreq sequence start whatever the current step was
                 self.seq_step = SequenceStep::HashFreq(0);
+                // the ramp only programs the PLL0Parameter, so reset any output divider left by a previous
+                // configuration. Always written, even if already null, as seen on captured sequences.
                 self.plls[BM1370_PLL_ID_HASH].set_out_div(BM1370_PLL_OUT_HASH, 0);
                 self.registers
                     .insert(PLL0Divider::ADDR, self.plls[BM1370_PLL_ID_HASH].divider())
@@ -1075,6 +2289,10 @@ impl Asic for BM1370 {
     /// assert_eq!(bm1370.split_nonce_between_chips_next(65, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x08, 0x0c, 0x80, 0x00, 0x0f, 0xc1, 0x00], delay_ms: 0}));
     /// assert_eq!(bm1370.split_nonce_between_chips_next(65, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x0a, 0x0c, 0x80, 0x00, 0x13, 0xb2, 0x0b], delay_ms: 0}));
     // assert_eq!(bm1370.split_nonce_between_chips_next(65, 2), None);
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// // 130 chips every 2 addresses do not fit in the 8 bits address space
+    /// assert_eq!(bm1370.split_nonce_between_chips_next(130, 2), None);
     /// ```
     fn split_nonce_between_chips_next(
         &mut self,
@@ -1093,7 +2311,7 @@ impl Asic for BM1370 {
                     cmd: Command::write_reg(
                         ChipNonceOffsetV2::ADDR,
                         cno,
-                        Destination::Chip((step * asic_addr_interval) as u8),
+                        Destination::Chip(chip_address(step, asic_addr_interval)?),
                     ),
                     delay_ms: 0,
                 })
@@ -1103,7 +2321,9 @@ impl Asic for BM1370 {
                 None
             }
             _ => {
-                // authorize a VersionRolling sequence start whatever the current step was
+                // do not start a sequence addressing chips out of the 8 bits address space
+                check_addr_interval(chain_asic_num, asic_addr_interval).ok()?;
+                // authorize a SplitNonce sequence start whatever the current step was
                 self.seq_step = SequenceStep::SplitNonce(1);
                 let cno = ChipNonceOffsetV2::new(0, chain_asic_num).val();
                 self.registers.insert(ChipNonceOffsetV2::ADDR, cno).unwrap();
@@ -1117,6 +2337,9 @@ impl Asic for BM1370 {
 
     /// ## Send Enable Version Rolling command list
     ///
+    /// Without a `variant`, the HashCountingNumber captured on S21 Pro is written.
+    /// Nothing is sent if `mask` is rejected by `check_version_mask()`.
+    ///
     /// ### Example
     /// ```
     /// use bm1370::BM1370;
@@ -1128,7 +2351,23 @@ impl Asic for BM1370 {
     /// assert_eq!(bm1370.set_version_rolling_next(0x1fff_e000), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0xa4, 0x90, 0x00, 0xff, 0xff, 0x1c], delay_ms: 1}));
     /// assert_eq!(bm1370.set_version_rolling_next(0x1fff_e000), None);
     /// ```
+    ///
+    /// With an explicit `variant`, or an invalid mask:
+    /// ```
+    /// use bm1370::{BoardVariant, BM1370};
+    /// use bm13xx_asic::{Asic, CmdDelay};
+    ///
+    /// let mut bm1370 = BM1370::default();
+    /// bm1370.variant = Some(BoardVariant::S21XP);
+    /// assert_eq!(bm1370.set_version_rolling_next(0x1fff_e000), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x10, 0x00, 0x00, 0x1a, 0x44, 0x17], delay_ms: 1})); // S21XP
+    /// // 17 bits mask
+    /// let mut bm1370 = BM1370::default();
+    /// assert_eq!(bm1370.set_version_rolling_next(0x3fff_e000), None);
+    /// assert_eq!(bm1370.registers, BM1370::default().registers); // nothing programmed
+    /// ```
     fn set_version_rolling_next(&mut self, mask: u32) -> Option<CmdDelay> {
+        // do not roll more version bits than the chips support
+        check_version_mask(mask).ok()?;
         match self.seq_step {
             SequenceStep::VersionRolling(step) => match step {
                 0 => {
@@ -1148,6 +2387,7 @@ impl Asic for BM1370 {
                 }
                 1 => {
                     self.seq_step = SequenceStep::None;
+                    debug_assert!(self.check_version_rolling(Some(mask)).is_ok());
                     None
                 }
                 _ => unreachable!(),
@@ -1155,8 +2395,10 @@ impl Asic for BM1370 {
             _ => {
                 // authorize a VersionRolling sequence start whatever the current step was
                 self.seq_step = SequenceStep::VersionRolling(0);
-                let hcn = 0x0000_1eb5; // S21Pro
-                                       // let hcn = 0x0000_1a44; // S21XP
+                let hcn = self
+                    .variant
+                    .unwrap_or(BoardVariant::S21Pro)
+                    .hash_counting_number();
                 self.registers
                     .insert(HashCountingNumber::ADDR, hcn)
                     .unwrap();
@@ -1167,4 +2409,53 @@ impl Asic for BM1370 {
             }
         }
     }
+
+    /// ## Get the Nonce layout
+    ///
+    /// Core ID is hardcoded in Nonce\[31:25\].
+    /// The Chip Address is not directly hardcoded: if the Nonce space has been splitted between chips
+    /// (usage of ChipNonceOffset), the Chip index is hardcoded left aligned in Nonce\[24:9\].
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let layout = BM1370::default().nonce_layout();
+    /// assert_eq!(layout.core_id.extract(0x12345678), 0x09);
+    /// assert_eq!(layout.core_id.extract(0x906732c8), 72); // first Bitaxe Block 853742
+    /// assert_eq!(layout.small_core_id, None);
+    /// assert_eq!(layout.chip_addr, None);
+    /// ```
+    fn nonce_layout(&self) -> NonceLayout {
+        NonceLayout {
+            core_id: NonceField::new(25, 7),
+            small_core_id: None,
+            chip_addr: None,
+            chip_addr_rolling: None,
+        }
+    }
+
+    /// ## Get the bring-up order
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1370::BM1370;
+    /// use bm13xx_asic::{Asic, BringUpStep};
+    ///
+    /// assert_eq!(
+    ///     BM1370::default().bring_up_order(),
+    ///     [
+    ///         BringUpStep::Reset,
+    ///         BringUpStep::Init,
+    ///         BringUpStep::ResetCore,
+    ///         BringUpStep::HashFreq,
+    ///         BringUpStep::Baudrate,
+    ///         BringUpStep::VersionRolling,
+    ///     ]
+    /// );
+    /// ```
+    fn bring_up_order(&self) -> &'static [BringUpStep] {
+        &BRING_UP_ORDER
+    }
 }
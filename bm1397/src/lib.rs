@@ -4,7 +4,14 @@
 #![macro_use]
 pub(crate) mod fmt;
 
-use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay, SequenceStep};
+use bm13xx_asic::{
+    core_register::*,
+    max_clki_baudrate,
+    nonce::{NonceField, NonceLayout},
+    pll::{pll_frequency, pll_roles, PllRole},
+    register::*,
+    Asic, ChipSnapshot, CmdDelay, SequenceStep,
+};
 use bm13xx_protocol::command::{Command, Destination};
 
 use fugit::HertzU64;
@@ -20,9 +27,53 @@ pub const BM1397_PLL_ID_HASH: usize = 0; // PLL0 is used for Hashing
 pub const BM1397_PLL_OUT_HASH: usize = 0; // specifically PLL0_OUT0 is used for Hashing
 pub const BM1397_PLL_ID_UART: usize = 3; // PLL3 can be used for UART Baudrate
 pub const BM1397_PLL_OUT_UART: usize = 4; // specifically PLL3_OUT4 can be used for UART Baudrate
+/// Role of each PLL.
+///
+/// ### Example
+/// ```
+/// use bm1397::{BM1397_PLL_ID_HASH, BM1397_PLL_ID_UART, BM1397_PLL_ROLES};
+/// use bm13xx_asic::pll::PllRole;
+///
+/// assert_eq!(BM1397_PLL_ROLES, [PllRole::Hash, PllRole::Auxiliary, PllRole::Auxiliary, PllRole::Uart]);
+/// assert_eq!(BM1397_PLL_ROLES[BM1397_PLL_ID_HASH], PllRole::Hash);
+/// assert_eq!(BM1397_PLL_ROLES[BM1397_PLL_ID_UART], PllRole::Uart);
+/// ```
+pub const BM1397_PLL_ROLES: [PllRole; BM1397_PLL_CNT] =
+    pll_roles(BM1397_PLL_ID_HASH, BM1397_PLL_ID_UART);
+
+// All Small Cores of the Core layout are enabled
+const _: () = core::assert!(BM1397_SMALL_CORE_CNT == BM1397_CORE_CNT * BM1397_CORE_SMALL_CORE_CNT);
+
+// Core ID, Small Core ID and Chip Address must fit in the Nonce
+const _: () = core::assert!(bm13xx_asic::nonce::nonce_chip_addr_shift(
+    BM1397_CORE_CNT.ilog2() + 1,
+    BM1397_CORE_SMALL_CORE_CNT.ilog2() + 1
+)
+.is_some());
+
+/// Capacity of the registers map.
+pub const BM1397_REGISTER_CAPACITY: usize = 64;
+/// Number of registers set by `reset()`.
+///
+/// The remaining `BM1397_REGISTER_CAPACITY - BM1397_DEFAULT_REGISTER_CNT` slots are free for
+/// user-added registers.
+///
+/// ### Example
+/// ```
+/// use bm1397::{BM1397, BM1397_DEFAULT_REGISTER_CNT, BM1397_REGISTER_CAPACITY};
+///
+/// let bm1397 = BM1397::default();
+/// assert_eq!(bm1397.registers.len(), BM1397_DEFAULT_REGISTER_CNT);
+/// assert_eq!(bm1397.registers.capacity(), BM1397_REGISTER_CAPACITY);
+/// assert_eq!(BM1397_REGISTER_CAPACITY - BM1397_DEFAULT_REGISTER_CNT, 27); // headroom
+/// ```
+pub const BM1397_DEFAULT_REGISTER_CNT: usize = 37;
+
+// The default registers must fit in the registers map
+const _: () = core::assert!(BM1397_DEFAULT_REGISTER_CNT < BM1397_REGISTER_CAPACITY);
 
 /// # BM1397
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 // #[cfg_attr(feature = "defmt-03", derive(defmt::Format))] // FnvIndexMap doesn't implement defmt
 pub struct BM1397 {
     seq_step: SequenceStep,
@@ -35,7 +86,7 @@ pub struct BM1397 {
     pub input_clock_freq: HertzU64,
     pub plls: [bm13xx_asic::pll::Pll; BM1397_PLL_CNT],
     pub chip_addr: u8,
-    pub registers: FnvIndexMap<u8, u32, 64>,
+    pub registers: FnvIndexMap<u8, u32, BM1397_REGISTER_CAPACITY>,
     pub core_registers: FnvIndexMap<u8, u8, 16>,
 }
 
@@ -61,14 +112,61 @@ impl BM1397 {
         self.chip_addr = chip_addr;
     }
 
-    pub fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
-        self.plls[BM1397_PLL_ID_HASH].set_frequency(
-            self.input_clock_freq,
-            BM1397_PLL_OUT_HASH,
-            freq,
-            true,
-        );
-        self
+    /// ## Set the Hash PLL dividers
+    ///
+    /// Bypass the frequency solver and program the Hash PLL with the given dividers, keeping
+    /// the current output divider. The dividers are checked, see `PllDividers::check()`.
+    /// Returns the PLL0Parameter write command.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{pll::PllDividers, Asic, CmdDelay, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// let cmd = bm1397.set_hash_pll_dividers(192, 2, 5, 1).unwrap(); // VCO at 2400 MHz, high band
+    /// assert_eq!(cmd, CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xd0, 0xc0, 0x02, 0x51, 0x13], delay_ms: 1000});
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::MHz(25)); // default output divider is kept
+    /// assert_eq!(
+    ///     bm1397.set_hash_pll_dividers(192, 2, 1, 5),
+    ///     Err(Error::InvalidPllDividers {
+    ///         dividers: PllDividers { fb_div: 192, ref_div: 2, post1_div: 1, post2_div: 5, out_div: 7 }
+    ///     })
+    /// );
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::MHz(25)); // unchanged
+    /// ```
+    pub fn set_hash_pll_dividers(
+        &mut self,
+        fb_div: u16,
+        ref_div: u8,
+        post1_div: u8,
+        post2_div: u8,
+    ) -> bm13xx_asic::Result<CmdDelay> {
+        let pll = &mut self.plls[BM1397_PLL_ID_HASH];
+        let dividers = bm13xx_asic::pll::PllDividers {
+            fb_div,
+            ref_div,
+            post1_div,
+            post2_div,
+            out_div: pll.out_div(BM1397_PLL_OUT_HASH),
+        };
+        dividers.check(self.input_clock_freq)?;
+        pll.set_dividers(BM1397_PLL_OUT_HASH, &dividers)
+            .enable()
+            .lock();
+        if pll.vco_freq(self.input_clock_freq) >= HertzU64::MHz(2400) {
+            pll.set_vco_high_freq();
+        } else {
+            pll.set_vco_low_freq();
+        }
+        self.registers
+            .insert(PLL0Parameter::ADDR, pll.parameter())
+            .unwrap();
+        Ok(CmdDelay {
+            cmd: Command::write_reg(PLL0Parameter::ADDR, pll.parameter(), Destination::All),
+            delay_ms: 1000,
+        })
     }
 
     /*
@@ -135,7 +233,7 @@ impl Default for BM1397 {
             input_clock_freq: HertzU64::MHz(25),
             plls: [bm13xx_asic::pll::Pll::default(); BM1397_PLL_CNT],
             chip_addr: 0,
-            registers: FnvIndexMap::<_, _, 64>::new(),
+            registers: FnvIndexMap::<_, _, BM1397_REGISTER_CAPACITY>::new(),
             core_registers: FnvIndexMap::<_, _, 16>::new(),
         };
         bm1397.reset();
@@ -143,16 +241,42 @@ impl Default for BM1397 {
     }
 }
 
+/// ## Take a snapshot of a BM1397
+///
+/// ### Example
+/// ```
+/// use bm1397::BM1397;
+/// use bm13xx_asic::{register::*, Asic, ChipSnapshot};
+/// use fugit::HertzU64;
+///
+/// let mut bm1397 = BM1397::default();
+/// bm1397.set_chip_addr(4);
+/// bm1397.set_hash_freq(HertzU64::MHz(400));
+/// let snap = ChipSnapshot::from(&bm1397);
+/// assert_eq!(snap.chip_id, 0x1397);
+/// assert_eq!(snap.chip_addr, 4);
+/// assert_eq!(snap.hash_freq_hz, 400_000_000);
+/// assert_eq!(snap.version_rolling, None);
+/// ```
+impl From<&BM1397> for ChipSnapshot {
+    fn from(bm: &BM1397) -> Self {
+        // version rolling is not handled by the BM1397
+        ChipSnapshot::from_asic(bm, bm.chip_addr, None)
+    }
+}
+
 impl Asic for BM1397 {
     /// ## Reset the Chip to default state
+    ///
+    /// The `input_clock_freq` is kept, the default PLL dividers reloaded here only give the expected
+    /// frequencies against the board CLKI.
     fn reset(&mut self) {
         self.seq_step = SequenceStep::default();
         self.sha = bm13xx_asic::sha::Sha::default();
-        self.input_clock_freq = HertzU64::MHz(25);
         self.plls = [bm13xx_asic::pll::Pll::default(); BM1397_PLL_CNT];
         self.chip_addr = 0;
-        self.registers = FnvIndexMap::<_, _, 64>::new();
-        self.core_registers = FnvIndexMap::<_, _, 16>::new();
+        self.registers.clear();
+        self.core_registers.clear();
 
         // Default PLLs Parameter
         self.plls[0].set_parameter(0xC060_0161);
@@ -361,18 +485,39 @@ impl Asic for BM1397 {
 
     /// ## Get the SHA Hashing Frequency
     ///
+    /// The PLL0_OUT0 divider from the PLL0Divider register is taken into account.
+    ///
     /// ### Example
     /// ```
-    /// use bm1397::BM1397;
+    /// use bm1397::{BM1397, BM1397_PLL_ID_HASH};
     /// use bm13xx_asic::Asic;
     /// use fugit::HertzU64;
     ///
     /// let mut bm1397 = BM1397::default();
-    /// assert_eq!(bm1397.hash_freq(), HertzU64::Hz(21428571));
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::Hz(21428571)); // PLL0_OUT0 divided by 8
+    /// bm1397.plls[BM1397_PLL_ID_HASH].set_divider(0x0304_0600);
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::Hz(171428571)); // PLL0_OUT0 not divided
+    /// bm1397.plls[BM1397_PLL_ID_HASH].set_divider(0x0304_0601);
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::Hz(85714285)); // PLL0_OUT0 divided by 2
     /// assert_eq!(bm1397.set_hash_freq(HertzU64::MHz(425)).hash_freq(), HertzU64::MHz(425));
     /// ```
     fn hash_freq(&self) -> HertzU64 {
-        self.plls[BM1397_PLL_ID_HASH].frequency(self.input_clock_freq, BM1397_PLL_OUT_HASH)
+        pll_frequency(
+            &self.plls,
+            self.input_clock_freq,
+            BM1397_PLL_ID_HASH,
+            BM1397_PLL_OUT_HASH,
+        )
+    }
+
+    fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
+        self.plls[BM1397_PLL_ID_HASH].set_frequency(
+            self.input_clock_freq,
+            BM1397_PLL_OUT_HASH,
+            freq,
+            true,
+        );
+        self
     }
 
     /// ## Init the Chip command list
@@ -545,8 +690,9 @@ impl Asic for BM1397 {
     ///
     /// ### Example
     /// ```
-    /// use bm1397::{BM1397, BM1397_PLL_ID_UART};
+    /// use bm1397::{BM1397, BM1397_PLL_ID_UART, BM1397_PLL_OUT_UART};
     /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use fugit::HertzU64;
     ///
     /// let mut bm1397 = BM1397::default();
     // // Seen on T17
@@ -570,6 +716,12 @@ impl Asic for BM1397 {
     // assert!(!bm1397.plls[BM1397_PLL_ID_UART].enabled());
     /// assert_eq!(bm1397.registers.get(&MiscControl::ADDR).unwrap(), &0x0000_3A01);
     // assert_eq!(bm1397.registers.get(&PLL3Parameter::ADDR).unwrap(), &0x0070_0111);
+    /// // 6 Mbaud cannot be reached from the default 100 MHz UART clock, other dividers are used
+    /// while bm1397.set_baudrate_next(6_000_000, 1, 1, 256).is_some() {}
+    /// assert_eq!(bm1397.plls[BM1397_PLL_ID_UART].frequency(bm1397.input_clock_freq, BM1397_PLL_OUT_UART), HertzU64::MHz(96));
+    /// assert_eq!(bm1397.registers.get(&PLL3Parameter::ADDR).unwrap(), &0xC060_0140);
+    /// assert_eq!(bm1397.registers.get(&FastUARTConfiguration::ADDR).unwrap(), &0x0400_000F);
+    /// assert_eq!(bm1397.registers.get(&MiscControl::ADDR).unwrap(), &0x0001_2701);
     /// ```
     fn set_baudrate_next(
         &mut self,
@@ -578,7 +730,7 @@ impl Asic for BM1397 {
         _domain_asic_cnt: usize,
         _asic_addr_interval: usize,
     ) -> Option<CmdDelay> {
-        if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+        if baudrate <= max_clki_baudrate(self.input_clock_freq) {
             let fbase = self.input_clock_freq.raw() as u32;
             let bt8d = (fbase / (8 * baudrate)) - 1;
             match self.seq_step {
@@ -637,9 +789,33 @@ impl Asic for BM1397 {
                 .set_post1_div(1)
                 .set_post2_div(1)
                 .set_out_div(BM1397_PLL_OUT_UART, pll3_div4);
-            let fbase = self.plls[BM1397_PLL_ID_UART]
-                .frequency(self.input_clock_freq, BM1397_PLL_OUT_UART)
-                .raw();
+            let mut fbase = pll_frequency(
+                &self.plls,
+                self.input_clock_freq,
+                BM1397_PLL_ID_UART,
+                BM1397_PLL_OUT_UART,
+            )
+            .raw();
+            if fbase % (2 * baudrate as u64) != 0 {
+                // default dividers cannot reach this baudrate exactly, look for other ones
+                let bt8d = (fbase / (2 * baudrate as u64)).max(1) - 1;
+                let target = HertzU64::Hz(2 * baudrate as u64 * (bt8d + 1));
+                if let Some(dividers) = bm13xx_asic::pll::Pll::solve_for_freq(
+                    self.input_clock_freq,
+                    target,
+                    BM1397_PLL_OUT_UART,
+                ) {
+                    self.plls[BM1397_PLL_ID_UART].set_dividers(BM1397_PLL_OUT_UART, &dividers);
+                    fbase = pll_frequency(
+                        &self.plls,
+                        self.input_clock_freq,
+                        BM1397_PLL_ID_UART,
+                        BM1397_PLL_OUT_UART,
+                    )
+                    .raw();
+                }
+            }
+            let pll3_div4 = self.plls[BM1397_PLL_ID_UART].out_div(BM1397_PLL_OUT_UART);
             match self.seq_step {
                 SequenceStep::Baudrate(step) => match step {
                     0 => {
@@ -731,6 +907,11 @@ impl Asic for BM1397 {
 
     /// ## Send Hash Frequency command list
     ///
+    /// Like the BM1366 and BM1370 ramps, the PLL0_OUT0 divider is first reset in the PLL0Divider
+    /// register, then the PLL0Parameter is stepped by 6.25 MHz up to the target frequency.
+    /// The PLL state is committed in the step emitting the matching PLL0Parameter.
+    /// Returns `None` without starting if the target frequency can not be reached.
+    ///
     /// ### Example
     /// ```
     /// use bm1397::{BM1397, BM1397_PLL_ID_HASH};
@@ -738,99 +919,63 @@ impl Asic for BM1397 {
     /// use fugit::HertzU64;
     ///
     /// let mut bm1397 = BM1397::default();
-    // assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(700)), CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x0f, 0x0f, 0x0f, 0x00, 25], delay_ms: 0});
-    // assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(700)), CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xad, 0x02, 0x77, 13], delay_ms: 1});
-    // assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(700)), CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x0f, 0x0f, 0x0f, 0x00, 25], delay_ms: 0});
-    // assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(700)), CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xad, 0x02, 0x76, 8], delay_ms: 1});
-    /// assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(700)), None);
-    // assert_eq!(bm1397.plls[BM1397_PLL_ID_HASH].parameter(), 0xc070_0111);
-    /// ```
-    fn set_hash_freq_next(&mut self, _target_freq: HertzU64) -> Option<CmdDelay> {
-        /*
-        self.plls[BM1397_PLL_ID_HASH].set_divider(0x0f0f_0f00);
-        self.registers
-            .insert(PLL0Divider::ADDR, self.plls[BM1397_PLL_ID_HASH].divider())
-            .unwrap();
-        self.plls[BM1397_PLL_ID_HASH]
-            .set_fb_div(173)
-            .set_ref_div(2)
-            .set_post1_div(7);
-        let mut prev_post_div: u8;
-        let mut found = false;
-        for post2_div in (1..=7).rev() {
-            prev_post_div = self.plls[BM1397_PLL_ID_HASH].post2_div();
-            self.plls[BM1397_PLL_ID_HASH].set_post2_div(post2_div);
-            if self.hash_freq() < target_freq {
-                Some(CmdDelay {
-                        cmd: Command::write_reg(
-                            PLL0Divider::ADDR,
-                            self.plls[BM1397_PLL_ID_HASH].divider(),
-                            Destination::All,
-                        ),
-                        delay_ms: 1,
-                    })
-                Some(CmdDelay {
-                        cmd: Command::write_reg(
-                            PLL0Parameter::ADDR,
-                            self.plls[BM1397_PLL_ID_HASH].parameter(),
-                            Destination::All,
-                        ),
-                        delay_ms: if post2_div == 1 {
-                            12000
-                        } else if post2_div == 2 {
-                            6000
-                        } else {
-                            1000
-                        },
-                    })
+    /// assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(425)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x03, 0x04, 0x06, 0x00, 0x0e], delay_ms: 1}));
+    /// assert_eq!(bm1397.registers.get(&PLL0Divider::ADDR).unwrap(), &0x0304_0600);
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::Hz(171_428_571)); // default PLL0Parameter
+    /// assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(425)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xab, 0x02, 0x51, 0x18], delay_ms: 400}));
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::kHz(178_125)); // closest to 177.68 MHz
+    /// let mut last = None;
+    /// let mut steps = 2;
+    /// while let Some(step) = bm1397.set_hash_freq_next(HertzU64::MHz(425)) {
+    ///     last = Some(step);
+    ///     steps += 1;
+    /// }
+    /// assert_eq!(steps, 42);
+    /// assert_eq!(last, Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xaa, 0x02, 0x40, 0x1f], delay_ms: 400}));
+    /// assert_eq!(bm1397.registers.get(&PLL0Parameter::ADDR).unwrap(), &0xc0aa_0240);
+    /// assert_eq!(bm1397.hash_freq(), HertzU64::MHz(425));
+    /// assert_eq!(bm1397.set_hash_freq_next(HertzU64::Hz(1)), None); // out of reach
+    /// ```
+    fn set_hash_freq_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
+        match self.seq_step {
+            SequenceStep::HashFreq(0) => {
+                let mut freq = self.hash_freq() + HertzU64::kHz(6250);
+                if freq >= target_freq {
+                    freq = target_freq;
+                    self.seq_step = SequenceStep::HashFreq(1);
+                }
+                self.set_hash_freq(freq);
+                let pll_param = self.plls[BM1397_PLL_ID_HASH].parameter();
                 self.registers
-                    .insert(
-                        PLL0Parameter::ADDR,
-                        self.plls[BM1397_PLL_ID_HASH].parameter(),
-                    )
+                    .insert(PLL0Parameter::ADDR, pll_param)
                     .unwrap();
-            } else {
-                self.plls[BM1397_PLL_ID_HASH].set_post2_div(prev_post_div);
-                found = true;
-                break;
+                Some(CmdDelay {
+                    cmd: Command::write_reg(PLL0Parameter::ADDR, pll_param, Destination::All),
+                    delay_ms: 400,
+                })
             }
-        }
-        if !found {
-            for post1_div in (1..=6).rev() {
-                prev_post_div = self.plls[BM1397_PLL_ID_HASH].post1_div();
-                self.plls[BM1397_PLL_ID_HASH].set_post2_div(post1_div);
-                if self.hash_freq() < target_freq {
-                    Some(CmdDelay {
-                            cmd: Command::write_reg(
-                                PLL0Divider::ADDR,
-                                self.plls[BM1397_PLL_ID_HASH].divider(),
-                                Destination::All,
-                            ),
-                            delay_ms: 1,
-                        })
-                    Some(CmdDelay {
-                            cmd: Command::write_reg(
-                                PLL0Parameter::ADDR,
-                                self.plls[BM1397_PLL_ID_HASH].parameter(),
-                                Destination::All,
-                            ),
-                            delay_ms: if post1_div > 4 { 6000 } else { 9000 },
-                        })
-                    self.registers
-                        .insert(
-                            PLL0Parameter::ADDR,
-                            self.plls[BM1397_PLL_ID_HASH].parameter(),
-                        )
-                        .unwrap();
-                } else {
-                    self.plls[BM1397_PLL_ID_HASH].set_post1_div(prev_post_div);
-                    break;
-                }
+            SequenceStep::HashFreq(_) => {
+                self.seq_step = SequenceStep::None;
+                None
+            }
+            _ => {
+                self.plls[BM1397_PLL_ID_HASH].needs_high_vco(
+                    self.input_clock_freq,
+                    BM1397_PLL_OUT_HASH,
+                    target_freq,
+                )?;
+                // authorize a HashFreq sequence start whatever the current step was
+                self.seq_step = SequenceStep::HashFreq(0);
+                // the ramp only programs the PLL0Parameter, so reset the output divider first
+                self.plls[BM1397_PLL_ID_HASH].set_out_div(BM1397_PLL_OUT_HASH, 0);
+                let pll_div = self.plls[BM1397_PLL_ID_HASH].divider();
+                self.registers.insert(PLL0Divider::ADDR, pll_div).unwrap();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(PLL0Divider::ADDR, pll_div, Destination::All),
+                    delay_ms: 1,
+                })
             }
         }
-        // self.set_hash_freq(target_freq); // TODO: finish with the closest value
-         */
-        None // TODO: impl
     }
 
     /// ## Send Split Nonce Between Chips command list
@@ -864,4 +1009,33 @@ impl Asic for BM1397 {
     fn set_version_rolling_next(&mut self, _mask: u32) -> Option<CmdDelay> {
         None
     }
+
+    /// ## Get the Nonce layout
+    ///
+    /// Core ID is hardcoded in Nonce\[31:24\], Small Core ID in Nonce\[23:22\]
+    /// and Chip Address in Nonce\[21:14\]. There is no Hardware Version Rolling.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let layout = BM1397::default().nonce_layout();
+    /// assert_eq!(layout.core_id.extract(0x12345678), 0x12);
+    /// let small_core_id = layout.small_core_id.unwrap();
+    /// assert_eq!(small_core_id.extract(0x12045678), 0);
+    /// assert_eq!(small_core_id.extract(0x12445678), 1);
+    /// assert_eq!(small_core_id.extract(0x12845678), 2);
+    /// assert_eq!(small_core_id.extract(0x12c45678), 3);
+    /// assert_eq!(layout.chip_addr.unwrap().extract(0x12345678), 0xD1);
+    /// assert_eq!(layout.chip_addr_rolling, None);
+    /// ```
+    fn nonce_layout(&self) -> NonceLayout {
+        NonceLayout {
+            core_id: NonceField::new(24, 8),
+            small_core_id: Some(NonceField::new(22, 2)),
+            chip_addr: Some(NonceField::new(14, 8)),
+            chip_addr_rolling: None,
+        }
+    }
 }
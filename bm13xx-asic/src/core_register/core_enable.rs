@@ -8,20 +8,42 @@ impl_boilerplate_for_core_reg!(CoreEnable);
 impl CoreEnable {
     pub const ID: u8 = 4;
 
-    // const CORE_EN_I_OFFSET: u8 = 0;
+    const CORE_EN_I_OFFSET: u8 = 0;
 
-    // const CORE_EN_I_MASK: u8 = 0xff;
+    const CORE_EN_I_MASK: u8 = 0xff;
+
+    /// ## Handle the CORE_EN_I field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::core_register::CoreEnable;
+    ///
+    /// let mut core_en = CoreEnable(0x00); // BM1366 default value
+    /// assert_eq!(core_en.core_en_i(), 0x00);
+    /// assert_eq!(core_en.set_core_en_i(0xff).core_en_i(), 0xff);
+    /// ```
+    pub const fn core_en_i(&self) -> u8 {
+        (self.0 >> Self::CORE_EN_I_OFFSET) & Self::CORE_EN_I_MASK
+    }
+    pub fn set_core_en_i(&mut self, core_en_i: u8) -> &mut Self {
+        self.0 &= !(Self::CORE_EN_I_MASK << Self::CORE_EN_I_OFFSET);
+        self.0 |= (core_en_i & Self::CORE_EN_I_MASK) << Self::CORE_EN_I_OFFSET;
+        self
+    }
 }
 
 impl ::core::fmt::Display for CoreEnable {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-        f.debug_struct("CoreEnable").finish()
+        f.debug_struct("CoreEnable")
+            .field("core_en_i", &self.core_en_i())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for CoreEnable {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "CoreEnable {{ }}",);
+        defmt::write!(fmt, "CoreEnable {{ core_en_i: {} }}", self.core_en_i());
     }
 }
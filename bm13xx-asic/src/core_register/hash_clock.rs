@@ -1,5 +1,8 @@
 use crate::core_register::CoreRegister;
 
+use core::time::Duration;
+use fugit::HertzU64;
+
 /// # Hash Clock Ctrl core register
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct HashClockCtrl(pub u8);
@@ -87,20 +90,72 @@ impl_boilerplate_for_core_reg!(HashClockCounter);
 impl HashClockCounter {
     pub const ID: u8 = 6;
 
-    // const CLOCK_CNT_OFFSET: u8 = 0;
+    const CLOCK_CNT_OFFSET: u8 = 0;
+
+    const CLOCK_CNT_MASK: u8 = 0xff;
+
+    /// ## Handle the clock count field.
+    ///
+    /// Number of Hash clock cycles counted, also used to configure the counting window.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::core_register::HashClockCounter;
+    ///
+    /// let mut hash_clock_counter = HashClockCounter(0x08); // BM1366 default value
+    /// assert_eq!(hash_clock_counter.clock_cnt(), 0x08);
+    /// assert_eq!(hash_clock_counter.set_clock_cnt(0).clock_cnt(), 0); // min value
+    /// assert_eq!(hash_clock_counter.set_clock_cnt(0xff).clock_cnt(), 0xff); // max value
+    /// ```
+    pub const fn clock_cnt(&self) -> u8 {
+        (self.0 >> Self::CLOCK_CNT_OFFSET) & Self::CLOCK_CNT_MASK
+    }
+    pub fn set_clock_cnt(&mut self, clock_cnt: u8) -> &mut Self {
+        self.0 &= !(Self::CLOCK_CNT_MASK << Self::CLOCK_CNT_OFFSET);
+        self.0 |= (clock_cnt & Self::CLOCK_CNT_MASK) << Self::CLOCK_CNT_OFFSET;
+        self
+    }
 
-    // const CLOCK_CNT_MASK: u8 = 0xff;
+    /// ## Get the PLL lock time measured by the counter.
+    ///
+    /// Convert the clock count in time at the given Hash frequency, `None` if the frequency is null.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::core_register::HashClockCounter;
+    /// use core::time::Duration;
+    /// use fugit::HertzU64;
+    ///
+    /// let hash_clock_counter = HashClockCounter(0x08); // BM1366 default value
+    /// assert_eq!(hash_clock_counter.lock_time(HertzU64::MHz(50)), Some(Duration::from_nanos(160)));
+    /// assert_eq!(hash_clock_counter.lock_time(HertzU64::MHz(400)), Some(Duration::from_nanos(20)));
+    /// assert_eq!(hash_clock_counter.lock_time(HertzU64::MHz(0)), None);
+    /// ```
+    pub fn lock_time(&self, hash_freq: HertzU64) -> Option<Duration> {
+        if hash_freq.raw() == 0 {
+            return None;
+        }
+        Some(Duration::from_nanos(
+            self.clock_cnt() as u64 * 1_000_000_000 / hash_freq.raw(),
+        ))
+    }
 }
 
 impl ::core::fmt::Display for HashClockCounter {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-        f.debug_struct("HashClockCounter").finish()
+        f.debug_struct("HashClockCounter")
+            .field("clock_cnt", &self.clock_cnt())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for HashClockCounter {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "HashClockCounter {{ }}",);
+        defmt::write!(
+            fmt,
+            "HashClockCounter {{ clock_cnt: {} }}",
+            self.clock_cnt(),
+        );
     }
 }
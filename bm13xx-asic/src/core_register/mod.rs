@@ -1,5 +1,10 @@
 //! BM13xx Core Registers.
 
+use crate::register::{CoreRegisterControl, CoreRegisterValue};
+use crate::{CmdDelay, Error, Result};
+use bm13xx_protocol::command::{Command, Destination};
+use heapless::FnvIndexMap;
+
 pub trait CoreRegister {
     fn id(&self) -> u8;
     fn val(&self) -> u8;
@@ -30,6 +35,84 @@ macro_rules! impl_boilerplate_for_core_reg {
     };
 }
 
+/// ## Set a Core Register command list
+///
+/// Write `value` in the Core Register `id` of all Cores of all chips, and keep track of it in the
+/// chip `core_registers` map. The value is only tracked once its command is in the returned list.
+///
+/// ### Errors
+///
+/// - Sequence too long if the command does not fit in `N` commands
+/// - Core register map full if `id` is not tracked yet and the map has no room left
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{core_register::*, CmdDelay, Error};
+/// use heapless::FnvIndexMap;
+///
+/// let mut core_registers = FnvIndexMap::<u8, u8, 16>::new();
+/// let seq = set_core_register::<1, 16>(&mut core_registers, HashClockCtrl::ID, 0x40).unwrap();
+/// assert_eq!(seq, [CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x85, 0x40, 0x0c], delay_ms: 10}]);
+/// assert_eq!(core_registers.get(&HashClockCtrl::ID), Some(&0x40));
+///
+/// assert_eq!(set_core_register::<0, 16>(&mut core_registers, HashClockCtrl::ID, 0x41), Err(Error::SequenceTooLong { capacity: 0 }));
+/// assert_eq!(core_registers.get(&HashClockCtrl::ID), Some(&0x40)); // untouched
+/// for id in 0x20..0x40 {
+///     core_registers.insert(id, 0x00).ok();
+/// }
+/// assert_eq!(set_core_register::<1, 16>(&mut core_registers, 0x7f, 0x00), Err(Error::CoreRegisterMapFull { id: 0x7f }));
+/// assert_eq!(core_registers.get(&0x7f), None);
+/// assert!(set_core_register::<1, 16>(&mut core_registers, HashClockCtrl::ID, 0x41).is_ok()); // already tracked
+/// ```
+pub fn set_core_register<const N: usize, const M: usize>(
+    core_registers: &mut FnvIndexMap<u8, u8, M>,
+    id: u8,
+    value: u8,
+) -> Result<heapless::Vec<CmdDelay, N>> {
+    let mut seq = heapless::Vec::new();
+    seq.push(CmdDelay {
+        cmd: Command::write_reg(
+            CoreRegisterControl::ADDR,
+            CoreRegisterControl::write_core_reg_id(0, id, value),
+            Destination::All,
+        ),
+        delay_ms: 10,
+    })
+    .map_err(|_| Error::SequenceTooLong { capacity: N })?;
+    core_registers
+        .insert(id, value)
+        .map_err(|_| Error::CoreRegisterMapFull { id })?;
+    Ok(seq)
+}
+
+/// ## Read a Core Register commands
+///
+/// Return the CoreRegisterControl write selecting the Core Register `id` to read,
+/// and the CoreRegisterValue read command returning its value.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{core_register::*, CmdDelay};
+/// use bm13xx_protocol::command::Destination;
+///
+/// let (select, read) = read_core_register(HashClockCtrl::ID, Destination::Chip(0));
+/// assert_eq!(select, CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x05, 0xff, 0x1b], delay_ms: 10});
+/// assert_eq!(read, [0x55, 0xaa, 0x42, 0x05, 0x00, 0x40, 0x09]);
+/// ```
+pub fn read_core_register(id: u8, dest: Destination) -> (CmdDelay, [u8; 7]) {
+    (
+        CmdDelay {
+            cmd: Command::write_reg(
+                CoreRegisterControl::ADDR,
+                CoreRegisterControl::read_core_reg_id(0, id),
+                dest,
+            ),
+            delay_ms: 10,
+        },
+        Command::read_reg(CoreRegisterValue::ADDR, dest),
+    )
+}
+
 mod clock_delay;
 mod core_enable;
 mod core_error;
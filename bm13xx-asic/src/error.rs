@@ -1,3 +1,4 @@
+use crate::{pll::PllDividers, BringUpStep};
 use derive_more::From;
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -6,15 +7,187 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum Error {
     // -- register
-    UnknownRegister { reg_addr: u8 },
+    UnknownRegister {
+        reg_addr: u8,
+    },
+    #[from(ignore)]
+    MissingRegister {
+        reg_addr: u8,
+    },
+    #[from(ignore)]
+    CoreRegisterMapFull {
+        id: u8,
+    },
+    #[from(ignore)]
+    VersionRollingMismatch {
+        expected: Option<u32>,
+        programmed: Option<u32>,
+    },
+    #[from(ignore)]
+    InvalidVersionMask {
+        mask: u32,
+    },
+    // -- pll
+    #[from(ignore)]
+    InvalidHashFreq {
+        freq_hz: u64,
+    },
+    #[from(ignore)]
+    InvalidPllDividers {
+        dividers: PllDividers,
+    },
+    // -- sequence
+    #[from(ignore)]
+    SequenceTooLong {
+        capacity: usize,
+    },
+    // -- chain
+    #[from(ignore)]
+    InvalidAddrInterval {
+        chip_count: usize,
+        asic_addr_interval: usize,
+    },
+    #[from(ignore)]
+    InvalidDomainCount {
+        domain_cnt: usize,
+        asic_cnt: usize,
+    },
+    #[from(ignore)]
+    StackupChipCount {
+        expected: usize,
+        detected: usize,
+    },
+    #[from(ignore)]
+    StackupChipAddr {
+        chip_index: usize,
+        expected: u8,
+        detected: u8,
+    },
+    // -- bring-up
+    #[from(ignore)]
+    BringUpOutOfOrder {
+        current: Option<BringUpStep>,
+        requested: BringUpStep,
+    },
 }
 
 #[rustversion::since(1.81)]
 impl core::error::Error for Error {}
 
-#[rustversion::since(1.81)]
+#[cfg(feature = "std")]
+#[rustversion::before(1.81)]
+impl std::error::Error for Error {}
+
+/// ### Example
+/// ```
+/// use bm13xx_asic::{pll::PllDividers, BringUpStep, Error};
+///
+/// let dividers = PllDividers { fb_div: 200, ref_div: 1, post1_div: 4, post2_div: 0, out_div: 4 };
+/// let messages = [
+///     (Error::UnknownRegister { reg_addr: 0xfc }, "unknown register 0xfc"),
+///     (Error::MissingRegister { reg_addr: 0x08 }, "register 0x08 is not present in the chip register map"),
+///     (
+///         Error::CoreRegisterMapFull { id: 0x3c },
+///         "core register 0x3c does not fit in the chip core register map",
+///     ),
+///     (
+///         Error::VersionRollingMismatch { expected: Some(0x1fff_e000), programmed: None },
+///         "version rolling mismatch: expected Some(1fffe000), programmed None",
+///     ),
+///     (Error::InvalidVersionMask { mask: 0xffff_ffff }, "invalid version rolling mask 0xffffffff"),
+///     (Error::InvalidHashFreq { freq_hz: 5_000_000_000 }, "invalid hash frequency 5000000000 Hz"),
+///     (
+///         Error::InvalidPllDividers { dividers },
+///         "invalid PLL dividers: fb_div 200, ref_div 1, post1_div 4, post2_div 0, out_div 4",
+///     ),
+///     (Error::SequenceTooLong { capacity: 16 }, "sequence does not fit in a capacity of 16"),
+///     (
+///         Error::InvalidAddrInterval { chip_count: 65, asic_addr_interval: 4 },
+///         "address interval 4 is invalid for 65 chips",
+///     ),
+///     (
+///         Error::InvalidDomainCount { domain_cnt: 0, asic_cnt: 65 },
+///         "0 domains are invalid for 65 chips",
+///     ),
+///     (
+///         Error::StackupChipCount { expected: 65, detected: 64 },
+///         "expected 65 chips in the stackup, detected 64",
+///     ),
+///     (
+///         Error::StackupChipAddr { chip_index: 3, expected: 0x0c, detected: 0x10 },
+///         "chip 3 expected at address 0x0c, detected at 0x10",
+///     ),
+///     (
+///         Error::BringUpOutOfOrder { current: Some(BringUpStep::Reset), requested: BringUpStep::HashFreq },
+///         "bring-up step HashFreq can not follow Some(Reset)",
+///     ),
+/// ];
+/// for (err, msg) in messages {
+///     assert_eq!(err.to_string(), msg);
+/// }
+/// ```
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Error::UnknownRegister { reg_addr } => write!(f, "unknown register {reg_addr:#04x}"),
+            Error::MissingRegister { reg_addr } => write!(
+                f,
+                "register {reg_addr:#04x} is not present in the chip register map"
+            ),
+            Error::CoreRegisterMapFull { id } => write!(
+                f,
+                "core register {id:#04x} does not fit in the chip core register map"
+            ),
+            Error::VersionRollingMismatch {
+                expected,
+                programmed,
+            } => write!(
+                f,
+                "version rolling mismatch: expected {expected:x?}, programmed {programmed:x?}"
+            ),
+            Error::InvalidVersionMask { mask } => {
+                write!(f, "invalid version rolling mask {mask:#010x}")
+            }
+            Error::InvalidHashFreq { freq_hz } => write!(f, "invalid hash frequency {freq_hz} Hz"),
+            Error::InvalidPllDividers { dividers } => write!(
+                f,
+                "invalid PLL dividers: fb_div {}, ref_div {}, post1_div {}, post2_div {}, out_div {}",
+                dividers.fb_div,
+                dividers.ref_div,
+                dividers.post1_div,
+                dividers.post2_div,
+                dividers.out_div
+            ),
+            Error::SequenceTooLong { capacity } => {
+                write!(f, "sequence does not fit in a capacity of {capacity}")
+            }
+            Error::InvalidAddrInterval {
+                chip_count,
+                asic_addr_interval,
+            } => write!(
+                f,
+                "address interval {asic_addr_interval} is invalid for {chip_count} chips"
+            ),
+            Error::InvalidDomainCount {
+                domain_cnt,
+                asic_cnt,
+            } => write!(f, "{domain_cnt} domains are invalid for {asic_cnt} chips"),
+            Error::StackupChipCount { expected, detected } => write!(
+                f,
+                "expected {expected} chips in the stackup, detected {detected}"
+            ),
+            Error::StackupChipAddr {
+                chip_index,
+                expected,
+                detected,
+            } => write!(
+                f,
+                "chip {chip_index} expected at address {expected:#04x}, detected at {detected:#04x}"
+            ),
+            Error::BringUpOutOfOrder { current, requested } => write!(
+                f,
+                "bring-up step {requested:?} can not follow {current:?}"
+            ),
+        }
     }
 }
@@ -2,18 +2,26 @@
 
 #![no_std]
 #![macro_use]
+
+#[cfg(feature = "std")]
+extern crate std;
+
 pub(crate) mod fmt;
 
 pub mod core_register;
 mod error;
+pub mod nonce;
 pub mod pll;
 pub mod register;
 pub mod sha;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 pub use self::error::{Error, Result};
 
-use bm13xx_protocol::command::Destination;
+use bm13xx_protocol::command::{Command, Destination};
 
+use core::time::Duration;
 use fugit::HertzU64;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -23,6 +31,158 @@ pub struct CmdDelay {
     pub delay_ms: u32,
 }
 
+impl CmdDelay {
+    /// ## Get the valid bytes of the command frame
+    ///
+    /// The frame length is given by its LEN byte (`cmd[3]`, not counting the 2 preamble bytes),
+    /// shorter frames like Read Register ones are padded in `cmd`.
+    /// The whole `cmd` is returned if the LEN byte does not describe a valid frame.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::CmdDelay;
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let write = CmdDelay { cmd: Command::write_reg(0x14, 0xff, Destination::All), delay_ms: 0 };
+    /// assert_eq!(write.bytes(), Command::write_reg(0x14, 0xff, Destination::All));
+    /// let mut cmd = [0; 11];
+    /// cmd[..7].copy_from_slice(&Command::read_reg(0x00, Destination::All));
+    /// let read = CmdDelay { cmd, delay_ms: 0 };
+    /// assert_eq!(read.bytes(), [0x55, 0xAA, 0x52, 0x05, 0x00, 0x00, 0x0A]);
+    /// assert_eq!(CmdDelay { cmd: [0; 11], delay_ms: 0 }.bytes().len(), 11);
+    /// ```
+    pub fn bytes(&self) -> &[u8] {
+        let len = self.cmd[3] as usize + 2;
+        if (4..=self.cmd.len()).contains(&len) {
+            &self.cmd[..len]
+        } else {
+            &self.cmd
+        }
+    }
+
+    /// ## Get the time to transmit the command frame on the UART
+    ///
+    /// Each valid byte (see `bytes()`) is sent with 10 bits (start, 8 data, stop) at `baudrate`.
+    /// Returns `Duration::ZERO` if `baudrate` is 0.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::CmdDelay;
+    /// use core::time::Duration;
+    ///
+    /// let step = CmdDelay { cmd: [0; 11], delay_ms: 10 };
+    /// assert_eq!(step.frame_tx_time(115_200), Duration::from_nanos(954_861));
+    /// assert_eq!(step.frame_tx_time(1_000_000), Duration::from_micros(110));
+    /// assert_eq!(step.frame_tx_time(0), Duration::ZERO);
+    /// ```
+    pub fn frame_tx_time(&self, baudrate: u32) -> Duration {
+        if baudrate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos(self.bytes().len() as u64 * 10 * 1_000_000_000 / baudrate as u64)
+    }
+
+    /// ## Send the command to another destination
+    ///
+    /// Rebuild a Write Register command frame (and its CRC) for `dest`.
+    /// Other command frames are returned unchanged.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::CmdDelay;
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let step = CmdDelay { cmd: Command::write_reg(0x14, 0xff, Destination::All), delay_ms: 10 };
+    /// let step = step.with_dest(Destination::Chip(8));
+    /// assert_eq!(step.cmd, [0x55, 0xAA, 0x41, 0x09, 0x08, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x13]);
+    /// assert_eq!(step.delay_ms, 10);
+    /// assert_eq!(step.with_dest(Destination::All).cmd, Command::write_reg(0x14, 0xff, Destination::All));
+    /// let other = CmdDelay { cmd: [0; 11], delay_ms: 0 };
+    /// assert_eq!(other.with_dest(Destination::Chip(8)), other);
+    /// ```
+    pub fn with_dest(&self, dest: Destination) -> CmdDelay {
+        // Write Register header, addressed to a single chip or to all chips
+        if (self.cmd[2] & !Command::CMD_ALL_CHIP) != Command::CMD_WRITE_REGISTER || self.cmd[3] != 9
+        {
+            return self.clone();
+        }
+        let reg_val = u32::from_be_bytes([self.cmd[6], self.cmd[7], self.cmd[8], self.cmd[9]]);
+        CmdDelay {
+            cmd: Command::write_reg(self.cmd[5], reg_val, dest),
+            delay_ms: self.delay_ms,
+        }
+    }
+}
+
+/// ## Get the total duration of a command sequence
+///
+/// Sum of the transmit time of each command frame at `baudrate` and of its delay.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{sequence_duration, CmdDelay};
+/// use core::time::Duration;
+///
+/// let seq = [
+///     CmdDelay { cmd: [0; 11], delay_ms: 10 },
+///     CmdDelay { cmd: [0; 11], delay_ms: 0 },
+/// ];
+/// assert_eq!(sequence_duration(&seq, 1_000_000), Duration::from_micros(10_220));
+/// assert_eq!(sequence_duration(&[], 1_000_000), Duration::ZERO);
+/// ```
+pub fn sequence_duration(seq: &[CmdDelay], baudrate: u32) -> Duration {
+    seq.iter()
+        .map(|step| step.frame_tx_time(baudrate) + Duration::from_millis(step.delay_ms as u64))
+        .sum()
+}
+
+/// ## Concatenate two command sequences
+///
+/// Append `b` to `a` dropping any command identical to the previous one
+/// (same register written with the same value to the same destination),
+/// the delay of a dropped command is added to the kept one.
+///
+/// ### Errors
+///
+/// - Sequence too long if the result doesn't fit in `N` commands
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{concat_optimized, CmdDelay, Error};
+/// use bm13xx_protocol::command::{Command, Destination};
+///
+/// let a = [
+///     CmdDelay { cmd: Command::write_reg(0x3C, 0x8000_8540, Destination::All), delay_ms: 0 },
+///     CmdDelay { cmd: Command::write_reg(0x28, 0x0000_0000, Destination::All), delay_ms: 10 },
+/// ];
+/// let b = [
+///     CmdDelay { cmd: Command::write_reg(0x28, 0x0000_0000, Destination::All), delay_ms: 20 },
+///     CmdDelay { cmd: Command::write_reg(0x28, 0x0000_0000, Destination::Chip(2)), delay_ms: 0 },
+/// ];
+/// let seq = concat_optimized::<4>(&a, &b).unwrap();
+/// assert_eq!(seq.len(), 3);
+/// assert_eq!(seq[1], CmdDelay { cmd: Command::write_reg(0x28, 0x0000_0000, Destination::All), delay_ms: 30 });
+/// assert_eq!(seq[2].cmd, Command::write_reg(0x28, 0x0000_0000, Destination::Chip(2)));
+/// assert_eq!(concat_optimized::<2>(&a, &b), Err(Error::SequenceTooLong { capacity: 2 }));
+/// ```
+pub fn concat_optimized<const N: usize>(
+    a: &[CmdDelay],
+    b: &[CmdDelay],
+) -> Result<heapless::Vec<CmdDelay, N>> {
+    let mut seq = heapless::Vec::<CmdDelay, N>::new();
+    for step in a.iter().chain(b.iter()) {
+        match seq.last_mut() {
+            Some(last) if last.cmd == step.cmd => {
+                last.delay_ms = last.delay_ms.saturating_add(step.delay_ms);
+            }
+            _ => seq
+                .push(step.clone())
+                .map_err(|_| Error::SequenceTooLong { capacity: N })?,
+        }
+    }
+    Ok(seq)
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum SequenceStep {
@@ -36,6 +196,371 @@ pub enum SequenceStep {
     VersionRolling(usize),
 }
 
+/// # Bring-up Step
+///
+/// High level step of a chain bring-up, each one running the matching `Asic::*_next()` sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BringUpStep {
+    /// Reset the chip state, see `Asic::reset()`
+    Reset,
+    /// See `Asic::init_next()`
+    Init,
+    /// Reset the cores between init and the frequency ramp, see `Asic::reset_core_next()`
+    ResetCore,
+    /// See `Asic::set_hash_freq_next()`
+    HashFreq,
+    /// See `Asic::set_baudrate_next()`
+    Baudrate,
+    /// See `Asic::set_version_rolling_next()`
+    VersionRolling,
+}
+
+/// Canonical bring-up order, see `Asic::bring_up_order()`.
+pub const BRING_UP_ORDER: [BringUpStep; 6] = [
+    BringUpStep::Reset,
+    BringUpStep::Init,
+    BringUpStep::ResetCore,
+    BringUpStep::HashFreq,
+    BringUpStep::Baudrate,
+    BringUpStep::VersionRolling,
+];
+
+/// # Bring-up Configuration
+///
+/// Last known good configuration of a chip, re-applied by `Asic::recover()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BringUpConfig {
+    /// Difficulty used to set the TicketMask
+    pub difficulty: u32,
+    /// Target Hash frequency
+    pub hash_freq: HertzU64,
+    /// Version rolling mask if HW version rolling is enabled
+    pub version_mask: Option<u32>,
+}
+
+/// # Chip Snapshot
+///
+/// Flat and chip independent view of a chip state, for monitoring purpose.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ChipSnapshot {
+    pub chip_id: u16,
+    pub chip_addr: u8,
+    pub hash_freq_hz: u64,
+    /// Version rolling mask if enabled
+    pub version_rolling: Option<u32>,
+    /// Theoretical Hashrate in GH/s
+    pub theoretical_hashrate: f32,
+}
+
+impl ChipSnapshot {
+    /// ## Take a snapshot of a chip
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, ChipSnapshot};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// dummy.hash_freq = HertzU64::MHz(500);
+    /// let snap = ChipSnapshot::from_asic(&dummy, 2, None);
+    /// assert_eq!(snap.chip_id, 0x1234);
+    /// assert_eq!(snap.chip_addr, 2);
+    /// assert_eq!(snap.hash_freq_hz, 500_000_000);
+    /// assert_eq!(snap.theoretical_hashrate, 2.0);
+    /// ```
+    pub fn from_asic<A: Asic>(asic: &A, chip_addr: u8, version_rolling: Option<u32>) -> Self {
+        let hash_freq_hz = asic.hash_freq().raw();
+        ChipSnapshot {
+            chip_id: asic.chip_id(),
+            chip_addr,
+            hash_freq_hz,
+            version_rolling,
+            theoretical_hashrate: asic.theoretical_hashrate_ghs(),
+        }
+    }
+}
+
+/// ## Get the recommended ASIC address interval for a chain
+///
+/// Chip addresses are spread evenly on the 8 bits address space: `256 / chip_count` rounded down
+/// to a power of two, as done on real hashboards.
+/// A single chip chain saturates to 128 and an empty or too long chain returns 0.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::recommended_addr_interval;
+///
+/// assert_eq!(recommended_addr_interval(0), 0);
+/// assert_eq!(recommended_addr_interval(1), 128);
+/// assert_eq!(recommended_addr_interval(2), 128);
+/// assert_eq!(recommended_addr_interval(3), 64);
+/// assert_eq!(recommended_addr_interval(65), 2); // S21Pro hashboard
+/// assert_eq!(recommended_addr_interval(256), 1);
+/// assert_eq!(recommended_addr_interval(257), 0);
+/// ```
+pub const fn recommended_addr_interval(chip_count: usize) -> u8 {
+    if chip_count == 0 || chip_count > 256 {
+        0
+    } else if chip_count == 1 {
+        128
+    } else {
+        1 << (256 / chip_count).ilog2()
+    }
+}
+
+/// ## Check an ASIC address interval for a chain
+///
+/// All the `chip_count` chip addresses must fit in the 8 bits address space,
+/// otherwise they wrap around and some chips share the same `Destination::Chip` address.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{check_addr_interval, recommended_addr_interval, Error};
+///
+/// assert_eq!(check_addr_interval(65, recommended_addr_interval(65) as usize), Ok(()));
+/// assert_eq!(check_addr_interval(65, 3), Ok(())); // last chip at 192
+/// assert_eq!(check_addr_interval(65, 4), Err(Error::InvalidAddrInterval { chip_count: 65, asic_addr_interval: 4 })); // last chip at 256 -> 0
+/// assert_eq!(check_addr_interval(2, 0), Err(Error::InvalidAddrInterval { chip_count: 2, asic_addr_interval: 0 }));
+/// assert_eq!(check_addr_interval(1, 256), Ok(())); // single chip at 0
+/// ```
+pub fn check_addr_interval(chip_count: usize, asic_addr_interval: usize) -> Result<()> {
+    if chip_count <= 1 {
+        return Ok(());
+    }
+    match (chip_count - 1).checked_mul(asic_addr_interval) {
+        Some(last_addr) if asic_addr_interval != 0 && last_addr <= u8::MAX as usize => Ok(()),
+        _ => Err(Error::InvalidAddrInterval {
+            chip_count,
+            asic_addr_interval,
+        }),
+    }
+}
+
+/// ## Check a chain geometry
+///
+/// Check that `chain_domain_cnt` voltage domains of `domain_asic_cnt` chips, addressed every
+/// `asic_addr_interval`, give valid chip addresses, as assumed by `Asic::set_baudrate_next()`.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{check_chain_geometry, Error};
+///
+/// assert_eq!(check_chain_geometry(13, 7, 2), Ok(())); // S21XP
+/// assert_eq!(check_chain_geometry(0, 7, 2), Err(Error::InvalidDomainCount { domain_cnt: 0, asic_cnt: 0 }));
+/// assert_eq!(check_chain_geometry(13, 0, 2), Err(Error::InvalidDomainCount { domain_cnt: 13, asic_cnt: 0 }));
+/// assert_eq!(check_chain_geometry(13, 7, 3), Err(Error::InvalidAddrInterval { chip_count: 91, asic_addr_interval: 3 })); // last chip at 270
+/// assert_eq!(check_chain_geometry(13, 7, 0), Err(Error::InvalidAddrInterval { chip_count: 91, asic_addr_interval: 0 }));
+/// ```
+pub fn check_chain_geometry(
+    chain_domain_cnt: usize,
+    domain_asic_cnt: usize,
+    asic_addr_interval: usize,
+) -> Result<()> {
+    let asic_cnt = chain_domain_cnt.saturating_mul(domain_asic_cnt);
+    if asic_cnt == 0 {
+        return Err(Error::InvalidDomainCount {
+            domain_cnt: chain_domain_cnt,
+            asic_cnt,
+        });
+    }
+    check_addr_interval(asic_cnt, asic_addr_interval)
+}
+
+/// Lowest legal Hash frequency.
+pub const HASH_FREQ_MIN: HertzU64 = HertzU64::MHz(50);
+/// Highest legal Hash frequency.
+pub const HASH_FREQ_MAX: HertzU64 = HertzU64::MHz(800);
+
+/// ## Check a Hash frequency
+///
+/// The Hash frequency must be in the `HASH_FREQ_MIN..=HASH_FREQ_MAX` band, where the Hash PLL
+/// can lock and the chips are known to hash.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{check_hash_freq, Error};
+/// use fugit::HertzU64;
+///
+/// assert_eq!(check_hash_freq(HertzU64::MHz(500)), Ok(()));
+/// assert_eq!(check_hash_freq(HertzU64::MHz(50)), Ok(()));
+/// assert_eq!(check_hash_freq(HertzU64::MHz(0)), Err(Error::InvalidHashFreq { freq_hz: 0 }));
+/// assert_eq!(check_hash_freq(HertzU64::MHz(2000)), Err(Error::InvalidHashFreq { freq_hz: 2_000_000_000 }));
+/// ```
+pub fn check_hash_freq(freq: HertzU64) -> Result<()> {
+    if freq < HASH_FREQ_MIN || freq > HASH_FREQ_MAX {
+        return Err(Error::InvalidHashFreq {
+            freq_hz: freq.raw(),
+        });
+    }
+    Ok(())
+}
+
+/// ## Get the maximum baudrate reachable from CLKI
+///
+/// The chips derive the UART clock directly from CLKI up to `input_clock_freq / 8`,
+/// above this crossover `set_baudrate_next()` switches to the PLL3 path.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::max_clki_baudrate;
+/// use fugit::HertzU64;
+///
+/// assert_eq!(max_clki_baudrate(HertzU64::MHz(25)), 3_125_000);
+/// assert_eq!(max_clki_baudrate(HertzU64::MHz(20)), 2_500_000);
+/// ```
+pub const fn max_clki_baudrate(input_clock_freq: HertzU64) -> u32 {
+    (input_clock_freq.raw() / 8) as u32
+}
+
+/// Widest Version rolling mask, the VersionRolling register holds 16 bits from Version\[28:13\].
+pub const VERSION_MASK_MAX: u32 = 0x1fff_e000;
+
+/// ## Check a Version rolling mask
+///
+/// The mask must not be null and must fit in `VERSION_MASK_MAX`, so at most 16 bits are rolled.
+/// Wider masks would be silently truncated by the VersionRolling register, and the rolled
+/// Version space would not match the mask anymore.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{check_version_mask, Error};
+///
+/// assert_eq!(check_version_mask(0x1fff_e000), Ok(())); // 16 bits
+/// assert_eq!(check_version_mask(0x0000_e000), Ok(()));
+/// assert_eq!(check_version_mask(0x3fff_e000), Err(Error::InvalidVersionMask { mask: 0x3fff_e000 })); // 17 bits
+/// assert_eq!(check_version_mask(0x1fff_f000), Err(Error::InvalidVersionMask { mask: 0x1fff_f000 })); // 17 bits
+/// assert_eq!(check_version_mask(0), Err(Error::InvalidVersionMask { mask: 0 }));
+/// ```
+pub fn check_version_mask(mask: u32) -> Result<()> {
+    if mask == 0 || mask & !VERSION_MASK_MAX != 0 {
+        return Err(Error::InvalidVersionMask { mask });
+    }
+    Ok(())
+}
+
+/// ## Get the address of the Nth chip of a chain
+///
+/// Chip addresses are assigned during enumeration every `asic_addr_interval`,
+/// return `None` if the address of the chip at `index` doesn't fit in the 8 bits address space.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::chip_address;
+///
+/// // S21XP: 13 domains of 7 chips every 2 addresses
+/// assert_eq!(chip_address(0, 2), Some(0x00)); // first chip
+/// assert_eq!(chip_address(6 * 7 + 3, 2), Some(0x5a)); // middle chip of the middle domain
+/// assert_eq!(chip_address(13 * 7 - 1, 2), Some(0xb4)); // last chip
+/// // largest chain
+/// assert_eq!(chip_address(255, 1), Some(0xff));
+/// assert_eq!(chip_address(256, 1), None);
+/// assert_eq!(chip_address(128, 2), None);
+/// assert_eq!(chip_address(usize::MAX, 2), None);
+/// ```
+pub const fn chip_address(index: usize, asic_addr_interval: usize) -> Option<u8> {
+    match index.checked_mul(asic_addr_interval) {
+        Some(addr) if addr <= u8::MAX as usize => Some(addr as u8),
+        _ => None,
+    }
+}
+
+/// ## Get the address of the Nth chip of a chain, checked
+///
+/// Same as `chip_address`, but an address not fitting in the 8 bits address space is reported
+/// as an invalid address interval for a chain of `chip_count` chips.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{chip_addr_checked, Error};
+///
+/// assert_eq!(chip_addr_checked(13 * 7 - 1, 2, 13 * 7), Ok(0xb4));
+/// assert_eq!(
+///     chip_addr_checked(128, 2, 130),
+///     Err(Error::InvalidAddrInterval { chip_count: 130, asic_addr_interval: 2 })
+/// );
+/// assert!(chip_addr_checked(usize::MAX, 2, usize::MAX).is_err());
+/// ```
+pub fn chip_addr_checked(index: usize, asic_addr_interval: usize, chip_count: usize) -> Result<u8> {
+    chip_address(index, asic_addr_interval).ok_or(Error::InvalidAddrInterval {
+        chip_count,
+        asic_addr_interval,
+    })
+}
+
+/// ## Get the index in the chain of a chip address
+///
+/// Inverse of `chip_address`, return `None` if `chip_addr` is not one of the addresses assigned
+/// every `asic_addr_interval` during enumeration.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{chip_address, chip_index};
+///
+/// assert_eq!(chip_index(0x5a, 2), Some(6 * 7 + 3));
+/// assert_eq!(chip_index(chip_address(90, 2).unwrap(), 2), Some(90));
+/// assert_eq!(chip_index(0x5b, 2), None); // not a chip address
+/// assert_eq!(chip_index(0x5a, 0), None);
+/// ```
+pub const fn chip_index(chip_addr: u8, asic_addr_interval: usize) -> Option<usize> {
+    if asic_addr_interval == 0 || chip_addr as usize % asic_addr_interval != 0 {
+        None
+    } else {
+        Some(chip_addr as usize / asic_addr_interval)
+    }
+}
+
+/// ## Validate a chain stackup
+///
+/// Check that the `detected_addrs` chip addresses match the layout assumed by the command sequences:
+/// `asic_cnt` chips evenly split in `domain_cnt` voltage domains, addressed every `asic_addr_interval`.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{validate_stackup, Error};
+///
+/// let addrs = [0x00, 0x04, 0x08, 0x0c, 0x10, 0x14, 0x18, 0x1c];
+/// assert_eq!(validate_stackup(&addrs, 4, 8, 4), Ok(()));
+/// assert_eq!(validate_stackup(&addrs, 3, 8, 4), Err(Error::InvalidDomainCount { domain_cnt: 3, asic_cnt: 8 }));
+/// assert_eq!(validate_stackup(&addrs[..7], 4, 8, 4), Err(Error::StackupChipCount { expected: 8, detected: 7 }));
+/// // chip at 0x0c is missing
+/// let addrs = [0x00, 0x04, 0x08, 0x10, 0x14, 0x18, 0x1c, 0x20];
+/// assert_eq!(validate_stackup(&addrs, 4, 8, 4), Err(Error::StackupChipAddr { chip_index: 3, expected: 0x0c, detected: 0x10 }));
+/// ```
+pub fn validate_stackup(
+    detected_addrs: &[u8],
+    domain_cnt: usize,
+    asic_cnt: usize,
+    asic_addr_interval: usize,
+) -> Result<()> {
+    if domain_cnt == 0 || asic_cnt % domain_cnt != 0 {
+        return Err(Error::InvalidDomainCount {
+            domain_cnt,
+            asic_cnt,
+        });
+    }
+    check_addr_interval(asic_cnt, asic_addr_interval)?;
+    if detected_addrs.len() != asic_cnt {
+        return Err(Error::StackupChipCount {
+            expected: asic_cnt,
+            detected: detected_addrs.len(),
+        });
+    }
+    for (chip_index, &detected) in detected_addrs.iter().enumerate() {
+        let expected = chip_addr_checked(chip_index, asic_addr_interval, asic_cnt)?;
+        if detected != expected {
+            return Err(Error::StackupChipAddr {
+                chip_index,
+                expected,
+                detected,
+            });
+        }
+    }
+    Ok(())
+}
+
 pub trait Asic {
     fn reset(&mut self);
     fn chip_id(&self) -> u16;
@@ -45,6 +570,7 @@ pub trait Asic {
     fn cno_interval(&self) -> usize;
     fn cno_bits(&self) -> u32;
     fn hash_freq(&self) -> HertzU64;
+    fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self;
     fn init_next(&mut self, difficulty: u32) -> Option<CmdDelay>;
     fn set_baudrate_next(
         &mut self,
@@ -61,4 +587,621 @@ pub trait Asic {
         asic_addr_interval: usize,
     ) -> Option<CmdDelay>;
     fn set_version_rolling_next(&mut self, mask: u32) -> Option<CmdDelay>;
+    fn nonce_layout(&self) -> nonce::NonceLayout;
+
+    /// ## Set the Hash Frequency, checking it first
+    ///
+    /// Like `set_hash_freq()`, but the frequency is checked with `check_hash_freq()` and the PLL
+    /// is left untouched if it is out of the legal band.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// assert_eq!(dummy.try_set_hash_freq(HertzU64::MHz(400)).unwrap().hash_freq(), HertzU64::MHz(400));
+    /// assert_eq!(dummy.try_set_hash_freq(HertzU64::MHz(0)).unwrap_err(), Error::InvalidHashFreq { freq_hz: 0 });
+    /// assert!(dummy.try_set_hash_freq(HertzU64::MHz(3000)).is_err());
+    /// assert_eq!(dummy.hash_freq(), HertzU64::MHz(400));
+    /// ```
+    fn try_set_hash_freq(&mut self, freq: HertzU64) -> Result<&mut Self> {
+        check_hash_freq(freq)?;
+        Ok(self.set_hash_freq(freq))
+    }
+
+    /// ## Get the bring-up order
+    ///
+    /// Order in which the bring-up steps must be applied, so a generic driver can iterate it.
+    /// Getting it wrong can leave the chain unresponsive. Defaults to `BRING_UP_ORDER`.
+    fn bring_up_order(&self) -> &'static [BringUpStep] {
+        &BRING_UP_ORDER
+    }
+
+    /// ## Recover the chip command list
+    ///
+    /// Re-apply the last known good configuration following `bring_up_order()`:
+    /// Init, Core reset, Hash frequency and Version rolling (if enabled in `cfg`).
+    /// Useful when a chip stopped returning nonces.
+    ///
+    /// The Reset and Baudrate steps are skipped: the chip state still matches the chain, which
+    /// keeps its addresses and baudrate.
+    ///
+    /// The command list is built against a clone of the chip which is only committed on success,
+    /// so after an error the chip is left untouched and a retry restarts from the first step.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::*, test_util::DummyAsic, Asic, BringUpConfig, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// let cfg = BringUpConfig { difficulty: 256, hash_freq: HertzU64::MHz(500), version_mask: Some(0x1fff_e000) };
+    /// let seq = dummy.recover::<8>(&cfg).unwrap();
+    /// assert_eq!(seq.len(), 8);
+    /// assert_eq!(seq[0].cmd, Command::write_reg(TicketMask::ADDR, 0xff, Destination::All));
+    /// assert_eq!(seq[2].cmd, Command::write_reg(RegA8::ADDR, 1, Destination::All));
+    /// assert_eq!(seq[7].cmd, Command::write_reg(VersionRolling::ADDR, 0x1fff_e000, Destination::All));
+    /// assert_eq!(dummy.hash_freq(), HertzU64::MHz(500));
+    /// let cfg = BringUpConfig { version_mask: None, hash_freq: HertzU64::MHz(400), ..cfg };
+    /// assert_eq!(dummy.recover::<8>(&cfg).unwrap().len(), 6);
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// assert_eq!(dummy.recover::<5>(&cfg), Err(Error::SequenceTooLong { capacity: 5 }));
+    /// assert_eq!(dummy, DummyAsic::default()); // left untouched
+    /// let seq = dummy.recover::<8>(&cfg).unwrap(); // retry restarts from the init
+    /// assert_eq!(seq[0].cmd, Command::write_reg(TicketMask::ADDR, 0xff, Destination::All));
+    /// assert_eq!(dummy.hash_freq(), HertzU64::MHz(400));
+    /// ```
+    fn recover<const N: usize>(&mut self, cfg: &BringUpConfig) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Clone + Sized,
+    {
+        let mut asic = self.clone();
+        let mut seq = heapless::Vec::new();
+        for step in self.bring_up_order() {
+            let steps = match step {
+                BringUpStep::Reset | BringUpStep::Baudrate => continue,
+                BringUpStep::Init => collect_sequence::<N>(|| asic.init_next(cfg.difficulty))?,
+                BringUpStep::ResetCore => {
+                    collect_sequence::<N>(|| asic.reset_core_next(Destination::All))?
+                }
+                BringUpStep::HashFreq => {
+                    collect_sequence::<N>(|| asic.set_hash_freq_next(cfg.hash_freq))?
+                }
+                BringUpStep::VersionRolling => match cfg.version_mask {
+                    Some(mask) => collect_sequence::<N>(|| asic.set_version_rolling_next(mask))?,
+                    None => continue,
+                },
+            };
+            seq.extend_from_slice(&steps)
+                .map_err(|_| Error::SequenceTooLong { capacity: N })?;
+        }
+        *self = asic;
+        Ok(seq)
+    }
+
+    /// ## Get the theoretical Hashrate in GH/s
+    ///
+    /// Every Small Core computes one hash per Hash clock cycle.
+    fn theoretical_hashrate_ghs(&self) -> f32 {
+        self.hash_freq().raw() as f32 * self.small_core_count() as f32 / 1_000_000_000.0
+    }
+
+    /// ## Get the Hashrate efficiency
+    ///
+    /// Ratio between the Hashrate measured by the chip, read from its HashRate register
+    /// (`measured_reg`), and the theoretical Hashrate at current Hash frequency.
+    /// Returns 0.0 if the theoretical Hashrate is null.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut dummy = DummyAsic::default(); // 4 Small Cores
+    /// dummy.hash_freq = HertzU64::Hz(1 << 30);
+    /// assert_eq!(dummy.hashrate_efficiency(0x0000_0100), 1.0); // 2^8 * 2^24 H/s
+    /// assert_eq!(dummy.hashrate_efficiency(0x8000_0080), 0.5);
+    /// assert_eq!(dummy.hashrate_efficiency(0x8000_0000), 0.0); // BM1397 default value
+    /// ```
+    fn hashrate_efficiency(&self, measured_reg: u32) -> f32 {
+        let theoretical = self.theoretical_hashrate_ghs();
+        if theoretical == 0.0 {
+            return 0.0;
+        }
+        register::HashRate(measured_reg).hashrate_ghs() / theoretical
+    }
+
+    /// ## Get the index of a Core in the whole chain
+    ///
+    /// Chain-wide Core index `chip_index * core_count + core_id`, the chip index being retrieved
+    /// from `chip_addr` with `chip_index()`.
+    /// Return `None` if `chip_addr` is not a chip address or `core_id` is out of bound.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic};
+    ///
+    /// let dummy = DummyAsic::bm1370_like();
+    /// // S21XP: 91 BM1370 every 2 addresses, Core 10 of the middle chip of the middle domain
+    /// assert_eq!(dummy.global_core_index(0x5a, 10, 2), Some(45 * 128 + 10));
+    /// assert_eq!(dummy.global_core_index(0xb4, 127, 2), Some(91 * 128 - 1)); // last Core of the chain
+    /// assert_eq!(dummy.global_core_index(0x5b, 10, 2), None);
+    /// assert_eq!(dummy.global_core_index(0x5a, 128, 2), None);
+    /// ```
+    fn global_core_index(
+        &self,
+        chip_addr: u8,
+        core_id: usize,
+        asic_addr_interval: usize,
+    ) -> Option<usize> {
+        if core_id >= self.core_count() {
+            return None;
+        }
+        Some(chip_index(chip_addr, asic_addr_interval)? * self.core_count() + core_id)
+    }
+
+    /// ## Get a suggested share difficulty
+    ///
+    /// Power of two difficulty, as supported by the TicketMask register, giving the closest share
+    /// rate to `target_shares_per_min` for `chip_count` chips hashing at the theoretical Hashrate.
+    /// A share at difficulty D needs D * 2^32 hashes on average.
+    /// The result is between 1 and 2^31.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic};
+    ///
+    /// let dummy = DummyAsic::bm1370_like();
+    /// // 1020 GH/s -> 1425 for 10 shares/min
+    /// assert_eq!(dummy.suggested_difficulty(10.0, 1), 1024);
+    /// assert_eq!(dummy.suggested_difficulty(10.0, 64), 65536);
+    /// assert_eq!(dummy.suggested_difficulty(1e12, 1), 1);
+    /// assert_eq!(dummy.suggested_difficulty(0.0, 1), 1 << 31);
+    /// ```
+    fn suggested_difficulty(&self, target_shares_per_min: f32, chip_count: usize) -> u32 {
+        const DIFF_MAX: u32 = 1 << 31;
+        if target_shares_per_min <= 0.0 {
+            return DIFF_MAX;
+        }
+        let hashrate = self.theoretical_hashrate_ghs() * 1e9 * chip_count as f32;
+        let diff = hashrate * 60.0 / target_shares_per_min / 4_294_967_296.0;
+        if diff < 1.0 {
+            return 1;
+        }
+        if diff >= DIFF_MAX as f32 {
+            return DIFF_MAX;
+        }
+        let lower = 1u32 << (diff as u32).ilog2();
+        // closest power of two on a logarithmic scale
+        if diff < lower as f32 * core::f32::consts::SQRT_2 {
+            lower
+        } else {
+            lower << 1
+        }
+    }
+
+    /// ## Get the expected Nonce interval
+    ///
+    /// Mean time between two shares returned by `chip_count` chips hashing at the theoretical
+    /// Hashrate with the given share `difficulty`, useful to tune a no-nonce watchdog.
+    /// A share at difficulty D needs D * 2^32 hashes on average.
+    /// Returns `Duration::MAX` if nothing is hashed.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic};
+    /// use core::time::Duration;
+    ///
+    /// let dummy = DummyAsic::bm1370_like();
+    /// // 65 chips at 1020 GH/s
+    /// assert_eq!(dummy.expected_nonce_interval(256, 65).as_micros(), 16_583);
+    /// assert_eq!(dummy.expected_nonce_interval(256, 1).as_micros(), 1_077_952);
+    /// assert_eq!(dummy.expected_nonce_interval(256, 0), Duration::MAX);
+    /// ```
+    fn expected_nonce_interval(&self, difficulty: u32, chip_count: usize) -> Duration {
+        let hashrate =
+            self.hash_freq().raw() as f64 * self.small_core_count() as f64 * chip_count as f64;
+        if hashrate == 0.0 {
+            return Duration::MAX;
+        }
+        Duration::from_secs_f64(difficulty as f64 * 4_294_967_296.0 / hashrate)
+    }
+
+    /// ## Preview the Init command list
+    ///
+    /// Build the same command list as `init_next()` against a clone of the chip,
+    /// so the chip state (registers, PLLs, sequence step) is left untouched.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::TicketMask, test_util::DummyAsic, Asic, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// let preview = dummy.preview_init::<4>(256).unwrap();
+    /// assert_eq!(preview.len(), 2);
+    /// assert_eq!(preview[1].cmd, Command::write_reg(TicketMask::ADDR, 0x0000_00ff, Destination::All));
+    /// assert_eq!(dummy, DummyAsic::default()); // left untouched
+    /// assert_eq!(dummy.preview_init::<1>(256), Err(Error::SequenceTooLong { capacity: 1 }));
+    /// while dummy.init_next(256).is_some() {}
+    /// assert_eq!(dummy.ticket_mask, 0x0000_00ff);
+    /// ```
+    fn preview_init<const N: usize>(&self, difficulty: u32) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Clone + Sized,
+    {
+        let mut asic = self.clone();
+        collect_sequence(|| asic.init_next(difficulty))
+    }
+
+    /// ## Init command list iterator
+    ///
+    /// Yield the `init_next()` commands one at a time, without building the whole command list.
+    fn init_frames(&mut self, difficulty: u32) -> impl Iterator<Item = CmdDelay> + '_
+    where
+        Self: Sized,
+    {
+        core::iter::from_fn(move || self.init_next(difficulty))
+    }
+
+    /// ## Init command list iterator with an observer
+    ///
+    /// Like `init_frames()`, but `observer` is called with each command as it is produced,
+    /// e.g. to trace the frame generation during bring-up. The yielded commands are unchanged.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic, CmdDelay};
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// dummy.seq_len = 3;
+    /// let mut observed = Vec::new();
+    /// let mut observer = |cmd: &CmdDelay| observed.push(cmd.cmd[5]);
+    /// let frames: Vec<CmdDelay> = dummy.init_frames_observed(256, &mut observer).collect();
+    /// assert_eq!(frames.len(), 3);
+    /// assert_eq!(observed, [0x14, 0x14, 0x14]); // called once per produced frame
+    /// ```
+    fn init_frames_observed<'a>(
+        &'a mut self,
+        difficulty: u32,
+        observer: &'a mut dyn FnMut(&CmdDelay),
+    ) -> impl Iterator<Item = CmdDelay> + 'a
+    where
+        Self: Sized,
+    {
+        self.init_frames(difficulty)
+            .inspect(move |cmd| observer(cmd))
+    }
+
+    /// ## Init a single Chip command list
+    ///
+    /// Build the `init_next()` command list targeting only the chip at `chip_addr`,
+    /// e.g. to re-init a replaced chip without disturbing the rest of the chain.
+    /// The command list is built against a clone of the chip which is only committed on success,
+    /// so after an error the chip is left untouched and a retry restarts the sequence.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::TicketMask, test_util::DummyAsic, Asic, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// let seq = dummy.init_chip::<4>(0x08, 256).unwrap();
+    /// assert_eq!(seq.len(), 2);
+    /// for step in seq.iter() {
+    ///     assert_eq!(step.cmd[2], 0x41); // Write Register to a single chip
+    ///     assert_eq!(step.cmd, Command::write_reg(TicketMask::ADDR, 0x0000_00ff, Destination::Chip(0x08)));
+    /// }
+    ///
+    /// let mut dummy = DummyAsic::default();
+    /// assert_eq!(dummy.init_chip::<1>(0x08, 256), Err(Error::SequenceTooLong { capacity: 1 }));
+    /// assert_eq!(dummy, DummyAsic::default()); // left untouched
+    /// assert_eq!(dummy.init_chip::<4>(0x08, 256).unwrap().len(), 2); // retry restarts the sequence
+    /// ```
+    fn init_chip<const N: usize>(
+        &mut self,
+        chip_addr: u8,
+        difficulty: u32,
+    ) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Clone + Sized,
+    {
+        let mut asic = self.clone();
+        let seq = collect_sequence(|| {
+            asic.init_next(difficulty)
+                .map(|step| step.with_dest(Destination::Chip(chip_addr)))
+        })?;
+        *self = asic;
+        Ok(seq)
+    }
+
+    /// ## Set the Hash Frequency of a single domain command list
+    ///
+    /// Build the `set_hash_freq_next()` command list ramping each chip of the voltage domain
+    /// `domain` from `from_freq` to `target_freq`, in lockstep: every ramp step is written to all
+    /// the chips of the domain, and its delay is applied after the last one.
+    /// The chip model tracks a single chain-wide frequency, so the ramp is built against a clone of
+    /// the chip brought to `from_freq` and the chip is left untouched: the caller keeps track of the
+    /// current frequency of each domain.
+    ///
+    /// ### Errors
+    ///
+    /// - Invalid address interval if a chip of the domain has no valid address
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::PLL0Parameter, test_util::DummyAsic, Asic, CmdDelay, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    /// use fugit::HertzU64;
+    ///
+    /// let dummy = DummyAsic::default(); // 50 MHz, 2 steps ramp
+    /// let pll = |freq, chip_addr, delay_ms| CmdDelay {
+    ///     cmd: Command::write_reg(PLL0Parameter::ADDR, freq, Destination::Chip(chip_addr)),
+    ///     delay_ms,
+    /// };
+    /// // domain 1 of a chain with 2 chips per domain, addressed every 32
+    /// let seq = dummy.set_hash_freq_domain::<8>(1, HertzU64::MHz(50), HertzU64::MHz(500), 2, 32).unwrap();
+    /// assert_eq!(seq, [pll(275, 64, 0), pll(275, 96, 10), pll(500, 64, 0), pll(500, 96, 10)]);
+    /// // domain 0 still runs at 50 MHz and ramps on its own
+    /// let seq = dummy.set_hash_freq_domain::<8>(0, HertzU64::MHz(50), HertzU64::MHz(400), 2, 32).unwrap();
+    /// assert_eq!(seq, [pll(225, 0, 0), pll(225, 32, 10), pll(400, 0, 0), pll(400, 32, 10)]);
+    /// // domain 1 ramps down from where it was left
+    /// let seq = dummy.set_hash_freq_domain::<8>(1, HertzU64::MHz(500), HertzU64::MHz(400), 2, 32).unwrap();
+    /// assert_eq!(seq, [pll(450, 64, 0), pll(450, 96, 10), pll(400, 64, 0), pll(400, 96, 10)]);
+    /// assert_eq!(dummy, DummyAsic::default()); // left untouched
+    /// assert_eq!(
+    ///     dummy.set_hash_freq_domain::<8>(4, HertzU64::MHz(50), HertzU64::MHz(400), 2, 32),
+    ///     Err(Error::InvalidAddrInterval { chip_count: 10, asic_addr_interval: 32 })
+    /// );
+    /// ```
+    fn set_hash_freq_domain<const N: usize>(
+        &self,
+        domain: usize,
+        from_freq: HertzU64,
+        target_freq: HertzU64,
+        domain_asic_cnt: usize,
+        asic_addr_interval: usize,
+    ) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Clone + Sized,
+    {
+        let first = domain * domain_asic_cnt;
+        let last = first + domain_asic_cnt;
+        check_addr_interval(last, asic_addr_interval)?;
+        let mut asic = self.clone();
+        while asic.set_hash_freq_next(from_freq).is_some() {}
+        let mut seq = heapless::Vec::new();
+        while let Some(step) = asic.set_hash_freq_next(target_freq) {
+            for asic_i in first..last {
+                let chip_addr = chip_addr_checked(asic_i, asic_addr_interval, last)?;
+                let mut cmd = step.with_dest(Destination::Chip(chip_addr));
+                if asic_i + 1 != last {
+                    cmd.delay_ms = 0;
+                }
+                seq.push(cmd)
+                    .map_err(|_| Error::SequenceTooLong { capacity: N })?;
+            }
+        }
+        Ok(seq)
+    }
+
+    /// ## Set Difficulty command list for a single Domain
+    ///
+    /// Write the TicketMask matching `difficulty` to each chip of `domain` only, so each
+    /// domain can run its own difficulty. All frames are sent without delay except the
+    /// last one of the domain.
+    /// The chip registers are left untouched, as they track the chain-wide TicketMask.
+    ///
+    /// ### Errors
+    ///
+    /// - Invalid address interval if a chip of the domain has no valid address
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::TicketMask, test_util::DummyAsic, Asic, CmdDelay, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let dummy = DummyAsic::default();
+    /// // domain 1 of a chain with 3 chips per domain, addressed every 4
+    /// let seq = dummy.set_difficulty_domain::<4>(1, 512, 3, 4).unwrap();
+    /// assert_eq!(
+    ///     seq,
+    ///     [
+    ///         CmdDelay { cmd: Command::write_reg(TicketMask::ADDR, 0x0000_80ff, Destination::Chip(12)), delay_ms: 0 },
+    ///         CmdDelay { cmd: Command::write_reg(TicketMask::ADDR, 0x0000_80ff, Destination::Chip(16)), delay_ms: 0 },
+    ///         CmdDelay { cmd: Command::write_reg(TicketMask::ADDR, 0x0000_80ff, Destination::Chip(20)), delay_ms: 10 },
+    ///     ]
+    /// );
+    /// assert_eq!(dummy.set_difficulty_domain::<2>(1, 512, 3, 4), Err(Error::SequenceTooLong { capacity: 2 }));
+    /// assert_eq!(
+    ///     dummy.set_difficulty_domain::<4>(4, 512, 2, 32),
+    ///     Err(Error::InvalidAddrInterval { chip_count: 10, asic_addr_interval: 32 })
+    /// );
+    /// ```
+    fn set_difficulty_domain<const N: usize>(
+        &self,
+        domain: usize,
+        difficulty: u32,
+        domain_asic_cnt: usize,
+        asic_addr_interval: usize,
+    ) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Sized,
+    {
+        let first = domain * domain_asic_cnt;
+        let last = first + domain_asic_cnt;
+        check_addr_interval(last, asic_addr_interval)?;
+        let tck_mask = register::TicketMask::from_difficulty(difficulty).0;
+        let mut seq = heapless::Vec::new();
+        for asic_i in first..last {
+            let chip_addr = chip_addr_checked(asic_i, asic_addr_interval, last)?;
+            seq.push(CmdDelay {
+                cmd: Command::write_reg(
+                    register::TicketMask::ADDR,
+                    tck_mask,
+                    Destination::Chip(chip_addr),
+                ),
+                delay_ms: if asic_i + 1 == last { 10 } else { 0 },
+            })
+            .map_err(|_| Error::SequenceTooLong { capacity: N })?;
+        }
+        Ok(seq)
+    }
+
+    /// ## Preview the Set Hash Frequency command list
+    ///
+    /// Build the same command list as `set_hash_freq_next()` against a clone of the chip,
+    /// so the chip state (registers, PLLs, sequence step) is left untouched.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::PLL0Parameter, test_util::DummyAsic, Asic, CmdDelay, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    /// use fugit::HertzU64;
+    ///
+    /// let dummy = DummyAsic::default(); // 50 MHz, 2 steps ramp
+    /// let pll = |freq| CmdDelay {
+    ///     cmd: Command::write_reg(PLL0Parameter::ADDR, freq, Destination::All),
+    ///     delay_ms: 10,
+    /// };
+    /// let seq = dummy.preview_hash_freq::<4>(HertzU64::MHz(500)).unwrap();
+    /// assert_eq!(seq, [pll(275), pll(500)]);
+    /// assert_eq!(dummy, DummyAsic::default()); // left untouched
+    /// assert_eq!(
+    ///     dummy.preview_hash_freq::<1>(HertzU64::MHz(500)),
+    ///     Err(Error::SequenceTooLong { capacity: 1 })
+    /// );
+    /// ```
+    fn preview_hash_freq<const N: usize>(
+        &self,
+        target_freq: HertzU64,
+    ) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Clone + Sized,
+    {
+        let mut asic = self.clone();
+        collect_sequence(|| asic.set_hash_freq_next(target_freq))
+    }
+
+    /// ## Preview the Set Baudrate command list
+    ///
+    /// Build the same command list as `set_baudrate_next()` against a clone of the chip,
+    /// so the chip state (registers, PLLs, sequence step) is left untouched.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the command list does not fit in `N` commands
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{register::FastUARTConfiguration, test_util::DummyAsic, Asic, CmdDelay, Error};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let dummy = DummyAsic::default(); // 2 frames per sequence
+    /// let uart = CmdDelay {
+    ///     cmd: Command::write_reg(FastUARTConfiguration::ADDR, 1_000_000, Destination::All),
+    ///     delay_ms: 10,
+    /// };
+    /// let seq = dummy.preview_baudrate::<4>(1_000_000, 1, 1, 256).unwrap();
+    /// assert_eq!(seq, [uart.clone(), uart]);
+    /// assert_eq!(dummy, DummyAsic::default()); // left untouched
+    /// assert_eq!(
+    ///     dummy.preview_baudrate::<1>(1_000_000, 1, 1, 256),
+    ///     Err(Error::SequenceTooLong { capacity: 1 })
+    /// );
+    /// ```
+    fn preview_baudrate<const N: usize>(
+        &self,
+        baudrate: u32,
+        chain_domain_cnt: usize,
+        domain_asic_cnt: usize,
+        asic_addr_interval: usize,
+    ) -> Result<heapless::Vec<CmdDelay, N>>
+    where
+        Self: Clone + Sized,
+    {
+        let mut asic = self.clone();
+        collect_sequence(|| {
+            asic.set_baudrate_next(
+                baudrate,
+                chain_domain_cnt,
+                domain_asic_cnt,
+                asic_addr_interval,
+            )
+        })
+    }
+
+    /// ## Get the Hash Frequency ramp profile
+    ///
+    /// Run the `set_hash_freq_next()` ramp against a clone of the chip and return the Hash frequency
+    /// reached after each command together with the command delay, to predict the bring-up timing.
+    ///
+    /// ### Errors
+    ///
+    /// - Sequence too long if the ramp does not fit in `N` steps
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{test_util::DummyAsic, Asic, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let dummy = DummyAsic::default(); // 50 MHz, 2 steps ramp
+    /// let profile = dummy.hash_freq_profile::<4>(HertzU64::MHz(500)).unwrap();
+    /// assert_eq!(profile, [(HertzU64::MHz(275), 10), (HertzU64::MHz(500), 10)]);
+    /// assert_eq!(dummy.hash_freq(), HertzU64::MHz(50)); // left untouched
+    /// assert!(dummy.hash_freq_profile::<4>(HertzU64::MHz(50)).unwrap().is_empty()); // already there
+    /// assert_eq!(
+    ///     dummy.hash_freq_profile::<1>(HertzU64::MHz(500)),
+    ///     Err(Error::SequenceTooLong { capacity: 1 })
+    /// );
+    /// ```
+    fn hash_freq_profile<const N: usize>(
+        &self,
+        target_freq: HertzU64,
+    ) -> Result<heapless::Vec<(HertzU64, u32), N>>
+    where
+        Self: Clone + Sized,
+    {
+        let mut asic = self.clone();
+        let mut profile = heapless::Vec::new();
+        while let Some(step) = asic.set_hash_freq_next(target_freq) {
+            profile
+                .push((asic.hash_freq(), step.delay_ms))
+                .map_err(|_| Error::SequenceTooLong { capacity: N })?;
+        }
+        Ok(profile)
+    }
+}
+
+/// Collect a whole command sequence from a `*_next()` step function.
+fn collect_sequence<const N: usize>(
+    mut next: impl FnMut() -> Option<CmdDelay>,
+) -> Result<heapless::Vec<CmdDelay, N>> {
+    let mut seq = heapless::Vec::new();
+    while let Some(step) = next() {
+        seq.push(step)
+            .map_err(|_| Error::SequenceTooLong { capacity: N })?;
+    }
+    Ok(seq)
 }
@@ -0,0 +1,593 @@
+use core::time::Duration;
+use fugit::HertzU64;
+use heapless::HistoryBuffer;
+
+/// # Nonce Field
+///
+/// Bit field of the Nonce hardcoded by the chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct NonceField {
+    /// Offset of the field LSB in the Nonce
+    pub offset: u32,
+    /// Width of the field in bits
+    pub width: u32,
+}
+
+impl NonceField {
+    pub const fn new(offset: u32, width: u32) -> Self {
+        Self { offset, width }
+    }
+
+    /// ## Extract the field value from a Nonce
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::NonceField;
+    ///
+    /// assert_eq!(NonceField::new(25, 7).extract(0x906732c8), 72); // Nonce[31:25]
+    /// assert_eq!(NonceField::new(14, 8).extract(0x12345678), 0xD1); // Nonce[21:14]
+    /// assert_eq!(NonceField::new(0, 32).extract(0x12345678), 0x12345678);
+    /// ```
+    pub const fn extract(&self, nonce: u32) -> usize {
+        let mask = if self.width >= u32::BITS {
+            u32::MAX
+        } else {
+            (1 << self.width) - 1
+        };
+        ((nonce >> self.offset) & mask) as usize
+    }
+}
+
+/// # Nonce Layout
+///
+/// Position of the fields hardcoded by a chip in the Nonces it returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct NonceLayout {
+    /// Core ID, always present
+    pub core_id: NonceField,
+    /// Small Core ID if the Hardware Version Rolling is disabled
+    /// (else it is in the Version, see `version2small_core_id`)
+    pub small_core_id: Option<NonceField>,
+    /// Chip Address if the Hardware Version Rolling is disabled
+    pub chip_addr: Option<NonceField>,
+    /// Chip Address if the Hardware Version Rolling is enabled
+    pub chip_addr_rolling: Option<NonceField>,
+}
+
+impl NonceLayout {
+    /// ## Narrow the Chip Address fields to the bits used by a chain
+    ///
+    /// The chips report an 8 bits Chip Address, but a chain of `chip_count` chips addressed
+    /// every `asic_addr_interval` only uses its `chip_addr_bits()` LSBs. Masking the unused MSBs
+    /// avoids attributing a Nonce to a chip out of the chain.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::{NonceField, NonceLayout};
+    ///
+    /// // BM1366 layout
+    /// let layout = NonceLayout {
+    ///     core_id: NonceField::new(25, 7),
+    ///     small_core_id: Some(NonceField::new(22, 3)),
+    ///     chip_addr: Some(NonceField::new(14, 8)),
+    ///     chip_addr_rolling: Some(NonceField::new(17, 8)),
+    /// };
+    /// // 65 chips addressed every 1 use 7 Chip Address bits
+    /// let chain_layout = layout.for_chain(65, 1);
+    /// assert_eq!(chain_layout.chip_addr, Some(NonceField::new(14, 7)));
+    /// assert_eq!(chain_layout.chip_addr_rolling, Some(NonceField::new(17, 7)));
+    /// let nonce = 0x0028_0000; // Chip Address field 0xa0
+    /// assert_eq!(layout.chip_addr.unwrap().extract(nonce), 0xa0); // out of the chain
+    /// assert_eq!(chain_layout.chip_addr.unwrap().extract(nonce), 0x20);
+    /// // 65 chips addressed every 2 use the 8 bits
+    /// assert_eq!(layout.for_chain(65, 2), layout);
+    /// ```
+    pub const fn for_chain(&self, chip_count: usize, asic_addr_interval: usize) -> Self {
+        let bits = chip_addr_bits(chip_count, asic_addr_interval);
+        Self {
+            core_id: self.core_id,
+            small_core_id: self.small_core_id,
+            chip_addr: narrow(self.chip_addr, bits),
+            chip_addr_rolling: narrow(self.chip_addr_rolling, bits),
+        }
+    }
+}
+
+/// Narrow an optional field to its `width` LSBs.
+const fn narrow(field: Option<NonceField>, width: u32) -> Option<NonceField> {
+    match field {
+        Some(field) if width < field.width => Some(NonceField::new(field.offset, width)),
+        _ => field,
+    }
+}
+
+/// ## Get the number of Chip Address bits used by a chain
+///
+/// Bits needed by the highest address of a chain of `chip_count` chips addressed every
+/// `asic_addr_interval`, saturating at the 8 bits Chip Address.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::chip_addr_bits;
+///
+/// assert_eq!(chip_addr_bits(65, 1), 7); // highest address 0x40
+/// assert_eq!(chip_addr_bits(65, 2), 8); // highest address 0x80
+/// assert_eq!(chip_addr_bits(13 * 7, 2), 8); // S21XP
+/// assert_eq!(chip_addr_bits(1, 2), 0);
+/// assert_eq!(chip_addr_bits(usize::MAX, 4), 8);
+/// ```
+pub const fn chip_addr_bits(chip_count: usize, asic_addr_interval: usize) -> u32 {
+    match chip_count.saturating_sub(1).checked_mul(asic_addr_interval) {
+        Some(max_addr) if max_addr <= u8::MAX as usize => usize::BITS - max_addr.leading_zeros(),
+        _ => u8::BITS,
+    }
+}
+
+/// ## Get the shift of the Chip Address field in a Nonce
+///
+/// When the Hardware Version Rolling is disabled, the Nonce is split from its MSB into
+/// the Core ID (`cores_bits`), the Small Core ID (`small_cores_bits`) and the 8 bits Chip Address.
+/// Returns `None` if these fields do not fit in the 32 bits Nonce, instead of underflowing.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::nonce_chip_addr_shift;
+///
+/// assert_eq!(nonce_chip_addr_shift(7, 3), Some(14)); // Nonce[31:25] Core ID, Nonce[24:22] Small Core ID
+/// assert_eq!(nonce_chip_addr_shift(8, 16), Some(0));
+/// assert_eq!(nonce_chip_addr_shift(8, 17), None);
+/// const _: () = assert!(nonce_chip_addr_shift(7, 3).is_some());
+/// ```
+pub const fn nonce_chip_addr_shift(cores_bits: u32, small_cores_bits: u32) -> Option<u32> {
+    let fields_bits = cores_bits + small_cores_bits + u8::BITS;
+    if fields_bits > u32::BITS {
+        None
+    } else {
+        Some(u32::BITS - fields_bits)
+    }
+}
+
+/// ## Get the number of Version bits rolled
+///
+/// Number of bits set in the HW version rolling mask, the chip rolling `2^n` Version combinations.
+/// Returns 0 if HW version rolling is disabled or the mask is empty: only the original Version is
+/// rolled.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::version_rolling_bits;
+///
+/// assert_eq!(version_rolling_bits(Some(0x1fff_e000)), 16);
+/// assert_eq!(version_rolling_bits(Some(0x0000_e000)), 3);
+/// assert_eq!(version_rolling_bits(Some(0)), 0);
+/// assert_eq!(version_rolling_bits(None), 0);
+/// ```
+pub const fn version_rolling_bits(version_rolling_mask: Option<u32>) -> u32 {
+    match version_rolling_mask {
+        Some(mask) => mask.count_ones(),
+        None => 0,
+    }
+}
+
+/// ## Get the Nonce search rate of a chain
+///
+/// Number of nonces searched per second by `chip_count` chips, each one rolling its `chip_nonce_space`
+/// every `rolling_duration`. If HW version rolling is enabled, each of the `2^n` Version combinations
+/// of the `n` bits set in the mask is rolled over the whole `chip_nonce_space`. An empty mask only
+/// rolls the original Version, like `None`.
+///
+/// This measures the Nonce space coverage, unlike the theoretical Hashrate which counts the SHA
+/// operations of all Small Cores.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::nonce_search_rate;
+/// use core::time::Duration;
+///
+/// // single chip rolling the full Nonce space in 1s
+/// assert_eq!(nonce_search_rate(1 << 32, None, Duration::from_secs(1), 1), 4_294_967_296.0);
+/// // single chip rolling the full Nonce space and 3 Version bits (8 combinations) in 8s
+/// assert_eq!(nonce_search_rate(1 << 32, Some(0x0000_e000), Duration::from_secs(8), 1), 4_294_967_296.0);
+/// // empty Version mask
+/// assert_eq!(nonce_search_rate(1 << 32, Some(0), Duration::from_secs(1), 1), 4_294_967_296.0);
+/// // 2 chips
+/// assert_eq!(nonce_search_rate(1 << 31, None, Duration::from_millis(500), 2), 8_589_934_592.0);
+/// assert_eq!(nonce_search_rate(1 << 32, None, Duration::ZERO, 1), 0.0);
+/// ```
+pub fn nonce_search_rate(
+    chip_nonce_space: usize,
+    version_rolling_mask: Option<u32>,
+    rolling_duration: Duration,
+    chip_count: usize,
+) -> f32 {
+    if rolling_duration.is_zero() {
+        return 0.0;
+    }
+    let version_space = 1u64 << version_rolling_bits(version_rolling_mask);
+    let space = chip_nonce_space as f32 * version_space as f32;
+    space * chip_count as f32 / rolling_duration.as_secs_f32()
+}
+
+/// ## Get the Nonce search rate of a Small Core
+///
+/// Number of nonces searched per second by each of the `small_core_cnt` Small Cores of a chip.
+/// The `chip_nonce_space` is partitioned between the Small Cores, each one computing one hash per
+/// Hash clock cycle over its share. If HW version rolling is enabled, each Nonce is tried for every
+/// Version combination before moving to the next one, so the Nonce rate is divided by the Version space.
+/// Returns 0.0 if a Small Core share is empty.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::small_core_nonce_rate;
+/// use fugit::HertzU64;
+///
+/// // 1024 Small Cores sharing the full Nonce space at 500 MHz
+/// assert_eq!(small_core_nonce_rate(HertzU64::MHz(500), 1 << 32, None, 1024), 500_000_000.0);
+/// // same with 3 Version bits, 8 Version combinations per Nonce
+/// assert_eq!(small_core_nonce_rate(HertzU64::MHz(500), 1 << 32, Some(0x0000_e000), 1024), 62_500_000.0);
+/// assert_eq!(small_core_nonce_rate(HertzU64::MHz(500), 1 << 32, None, 0), 0.0);
+/// assert_eq!(small_core_nonce_rate(HertzU64::MHz(500), 512, None, 1024), 0.0);
+/// ```
+pub fn small_core_nonce_rate(
+    hash_freq: HertzU64,
+    chip_nonce_space: usize,
+    version_rolling_mask: Option<u32>,
+    small_core_cnt: usize,
+) -> f32 {
+    if small_core_cnt == 0 || chip_nonce_space / small_core_cnt == 0 {
+        return 0.0;
+    }
+    let version_space = 1u64 << version_rolling_bits(version_rolling_mask);
+    hash_freq.raw() as f32 / version_space as f32
+}
+
+/// ## Get the number of bits rolled per chip
+///
+/// Base-2 logarithm (rounded down) of the space rolled by a single chip every `rolling_duration`:
+/// its `chip_nonce_space`, plus the `version_rolling_bits()` if HW version rolling is enabled,
+/// following the same math as `nonce_search_rate`.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::rolled_bits;
+///
+/// assert_eq!(rolled_bits(1 << 14, None), 14);
+/// // 2^16 Version combinations on top of the Nonce space
+/// assert_eq!(rolled_bits(1 << 14, Some(0x1fff_e000)), 30);
+/// assert_eq!(rolled_bits(1 << 14, Some(0)), 14);
+/// assert_eq!(rolled_bits(0, None), 0);
+/// ```
+pub fn rolled_bits(chip_nonce_space: usize, version_rolling_mask: Option<u32>) -> usize {
+    chip_nonce_space.checked_ilog2().map_or(0, |bits| {
+        (bits + version_rolling_bits(version_rolling_mask)) as usize
+    })
+}
+
+/// ## Get the fraction of the Nonce space covered by a chain
+///
+/// The Chip Nonce Offset space (`cno_bits` wide) is split between `chip_count` chips with an
+/// integer division, so each chip gets `2^cno_bits / chip_count` offsets and the remainder
+/// is never scanned. Returns 1.0 when `chip_count` divides the space evenly, 0.0 for an empty chain.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::nonce_space_coverage;
+///
+/// assert_eq!(nonce_space_coverage(16, 64), 1.0);
+/// // 65536 / 65 = 1008 offsets per chip, 16 offsets are lost
+/// assert_eq!(nonce_space_coverage(16, 65), 65520.0 / 65536.0);
+/// assert!(nonce_space_coverage(16, 65) < 1.0);
+/// assert_eq!(nonce_space_coverage(16, 0), 0.0);
+/// ```
+pub fn nonce_space_coverage(cno_bits: u32, chip_count: usize) -> f32 {
+    if chip_count == 0 {
+        return 0.0;
+    }
+    let space = 1u64 << cno_bits;
+    let share = space / chip_count as u64;
+    (share * chip_count as u64) as f32 / space as f32
+}
+
+/// ## Get the Small Core ID that produced a given Version
+///
+/// If the Hardware Version Rolling is enabled, the Small Core ID is hardcoded in the lowest rolled
+/// Version bits, starting at `version_mask.trailing_zeros()`.
+/// The field width is `log2(core_small_core_cnt)` bits, reduced to the number of contiguous bits set
+/// at the bottom of the mask: the mask must be contiguous on its lowest bits to carry the full Small Core ID.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::nonce::version2small_core_id;
+///
+/// // standard mask 0x1fffe000 with 8 Small Cores per Core: Version[15:13]
+/// assert_eq!(version2small_core_id(0x1fff_0000, 0x1fff_e000, 8), 0);
+/// assert_eq!(version2small_core_id(0x1fff_2000, 0x1fff_e000, 8), 1);
+/// assert_eq!(version2small_core_id(0x1fff_e000, 0x1fff_e000, 8), 7);
+/// assert_eq!(version2small_core_id(0x00f9_4000, 0x1fff_e000, 8), 2); // first Bitaxe Block 853742
+/// // 4 Small Cores per Core: Version[14:13]
+/// assert_eq!(version2small_core_id(0x1fff_6000, 0x1fff_e000, 4), 3);
+/// assert_eq!(version2small_core_id(0x1fff_8000, 0x1fff_e000, 4), 0);
+/// // narrow mask with only 2 bits at its bottom: Version[17:16]
+/// assert_eq!(version2small_core_id(0x0003_0000, 0x1ff3_0000, 8), 3);
+/// assert_eq!(version2small_core_id(0x0006_0000, 0x1ff3_0000, 8), 2);
+/// assert_eq!(version2small_core_id(0x1fff_e000, 0x0000_0000, 8), 0); // no rolling
+/// assert_eq!(version2small_core_id(0x1fff_e000, 0x1fff_e000, 1), 0); // single Small Core
+/// ```
+pub const fn version2small_core_id(
+    version: u32,
+    version_mask: u32,
+    core_small_core_cnt: usize,
+) -> usize {
+    if version_mask == 0 || core_small_core_cnt == 0 {
+        return 0;
+    }
+    let offset = version_mask.trailing_zeros();
+    let mask_bits = (version_mask >> offset).trailing_ones();
+    let small_core_bits = if core_small_core_cnt.ilog2() < mask_bits {
+        core_small_core_cnt.ilog2()
+    } else {
+        mask_bits
+    };
+    if small_core_bits == 0 {
+        return 0;
+    }
+    ((version >> offset) & (u32::MAX >> (u32::BITS - small_core_bits))) as usize
+}
+
+/// # Nonce Deduper
+///
+/// Remember the last `N` `(chip_addr, nonce)` pairs seen on the chain to detect
+/// chips returning the same nonce twice.
+#[derive(Debug, Clone)]
+pub struct NonceDeduper<const N: usize> {
+    seen: HistoryBuffer<(u8, u32), N>,
+}
+
+impl<const N: usize> NonceDeduper<N> {
+    pub const fn new() -> Self {
+        NonceDeduper {
+            seen: HistoryBuffer::new(),
+        }
+    }
+
+    /// ## Check if a nonce is a duplicate and remember it
+    ///
+    /// Return `true` if the `(chip_addr, nonce)` pair is already in the window.
+    /// A duplicate is not stored again, so it does not push older entries out.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::NonceDeduper;
+    ///
+    /// let mut dedup = NonceDeduper::<2>::new();
+    /// assert!(!dedup.is_duplicate(0x00, 0x1234_5678));
+    /// assert!(dedup.is_duplicate(0x00, 0x1234_5678));
+    /// assert!(!dedup.is_duplicate(0x02, 0x1234_5678)); // same nonce, other chip
+    /// assert!(!dedup.is_duplicate(0x00, 0x9abc_def0)); // 0x1234_5678 leaves the window
+    /// assert!(!dedup.is_duplicate(0x00, 0x1234_5678));
+    /// ```
+    pub fn is_duplicate(&mut self, chip_addr: u8, nonce: u32) -> bool {
+        if self.contains(chip_addr, nonce) {
+            return true;
+        }
+        self.seen.write((chip_addr, nonce));
+        false
+    }
+
+    /// ## Check if a nonce is in the window without remembering it
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::NonceDeduper;
+    ///
+    /// let mut dedup = NonceDeduper::<4>::new();
+    /// assert!(!dedup.contains(0x00, 0x1234_5678));
+    /// dedup.is_duplicate(0x00, 0x1234_5678);
+    /// assert!(dedup.contains(0x00, 0x1234_5678));
+    /// ```
+    pub fn contains(&self, chip_addr: u8, nonce: u32) -> bool {
+        self.seen.as_slice().contains(&(chip_addr, nonce))
+    }
+
+    /// ## Forget all the remembered nonces
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::NonceDeduper;
+    ///
+    /// let mut dedup = NonceDeduper::<4>::new();
+    /// dedup.is_duplicate(0x00, 0x1234_5678);
+    /// dedup.clear();
+    /// assert!(!dedup.is_duplicate(0x00, 0x1234_5678));
+    /// ```
+    pub fn clear(&mut self) {
+        self.seen.clear();
+    }
+
+    /// ## Get the number of nonces currently in the window
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::NonceDeduper;
+    ///
+    /// let mut dedup = NonceDeduper::<2>::new();
+    /// assert_eq!(dedup.len(), 0);
+    /// dedup.is_duplicate(0x00, 0x1111_1111);
+    /// dedup.is_duplicate(0x00, 0x2222_2222);
+    /// dedup.is_duplicate(0x00, 0x3333_3333);
+    /// assert_eq!(dedup.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.len() == 0
+    }
+
+    /// ## Get the size of the window
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::NonceDeduper;
+    ///
+    /// let dedup = NonceDeduper::<16>::new();
+    /// assert_eq!(dedup.capacity(), 16);
+    /// ```
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for NonceDeduper<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "defmt-03")]
+impl<const N: usize> defmt::Format for NonceDeduper<N> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "NonceDeduper {{ len: {}, capacity: {} }}",
+            self.len(),
+            N
+        );
+    }
+}
+
+/// # Core Statistics
+///
+/// Number of nonces submitted by a single Core and how many of them failed the target.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct CoreStats {
+    pub submitted: u32,
+    pub errors: u32,
+}
+
+/// # Core Error Statistics
+///
+/// Accumulate per-Core hardware-error counts to find weak Cores, `C` being the number of Cores in the ASIC.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct CoreErrorStats<const C: usize> {
+    cores: [CoreStats; C],
+}
+
+impl<const C: usize> CoreErrorStats<C> {
+    pub const fn new() -> Self {
+        CoreErrorStats {
+            cores: [CoreStats {
+                submitted: 0,
+                errors: 0,
+            }; C],
+        }
+    }
+
+    /// ## Record a nonce returned by a Core
+    ///
+    /// `valid` tells if the nonce meets the target, otherwise it is counted as an hardware error.
+    /// Nonces from an out of range `core_id` are ignored.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::{CoreErrorStats, CoreStats};
+    ///
+    /// let mut stats = CoreErrorStats::<4>::new();
+    /// stats.record(1, true);
+    /// stats.record(1, false);
+    /// stats.record(3, false);
+    /// stats.record(4, false); // out of range
+    /// assert_eq!(stats.core(1), Some(CoreStats { submitted: 2, errors: 1 }));
+    /// assert_eq!(stats.core(3), Some(CoreStats { submitted: 1, errors: 1 }));
+    /// assert_eq!(stats.core(4), None);
+    /// ```
+    pub fn record(&mut self, core_id: usize, valid: bool) -> &mut Self {
+        if let Some(core) = self.cores.get_mut(core_id) {
+            core.submitted = core.submitted.saturating_add(1);
+            if !valid {
+                core.errors = core.errors.saturating_add(1);
+            }
+        }
+        self
+    }
+
+    /// ## Get the statistics of a Core
+    pub fn core(&self, core_id: usize) -> Option<CoreStats> {
+        self.cores.get(core_id).copied()
+    }
+
+    /// ## Get the total number of nonces submitted by all Cores
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::{CoreErrorStats, CoreStats};
+    ///
+    /// let mut stats = CoreErrorStats::<4>::new();
+    /// stats.record(0, true).record(2, false).record(2, true);
+    /// assert_eq!(stats.total(), CoreStats { submitted: 3, errors: 1 });
+    /// ```
+    pub fn total(&self) -> CoreStats {
+        self.cores
+            .iter()
+            .fold(CoreStats::default(), |acc, core| CoreStats {
+                submitted: acc.submitted.saturating_add(core.submitted),
+                errors: acc.errors.saturating_add(core.errors),
+            })
+    }
+
+    /// ## Get the `K` Cores with the most errors
+    ///
+    /// Cores are sorted by decreasing error count, Cores without error are skipped.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::{CoreErrorStats, CoreStats};
+    ///
+    /// let mut stats = CoreErrorStats::<8>::new();
+    /// stats.record(2, false).record(2, false).record(2, true);
+    /// stats.record(5, false).record(5, false).record(5, false);
+    /// stats.record(6, false);
+    /// stats.record(7, true);
+    /// let worst = stats.worst_cores::<2>();
+    /// assert_eq!(worst.len(), 2);
+    /// assert_eq!(worst[0], (5, CoreStats { submitted: 3, errors: 3 }));
+    /// assert_eq!(worst[1], (2, CoreStats { submitted: 3, errors: 2 }));
+    /// assert_eq!(stats.worst_cores::<8>().len(), 3);
+    /// ```
+    pub fn worst_cores<const K: usize>(&self) -> heapless::Vec<(usize, CoreStats), K> {
+        let mut worst = heapless::Vec::<(usize, CoreStats), K>::new();
+        for (core_id, core) in self.cores.iter().enumerate() {
+            if core.errors == 0 {
+                continue;
+            }
+            let pos = worst
+                .iter()
+                .position(|(_, w)| core.errors > w.errors)
+                .unwrap_or(worst.len());
+            if pos == K {
+                continue;
+            }
+            if worst.is_full() {
+                worst.pop();
+            }
+            worst.insert(pos, (core_id, *core)).ok();
+        }
+        worst
+    }
+
+    /// ## Reset all the counters
+    pub fn clear(&mut self) {
+        self.cores = [CoreStats::default(); C];
+    }
+}
+
+impl<const C: usize> Default for CoreErrorStats<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
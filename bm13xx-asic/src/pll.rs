@@ -1,11 +1,131 @@
 use core::f64;
 
+use bm13xx_protocol::{
+    command::{Command, Destination},
+    response::RegisterResponse,
+};
 use fugit::HertzU64;
+use heapless::Vec;
 
 pub const PLL_OUT_MAX: usize = 5;
 const PLL_VCO_FREQ_MAX: HertzU64 = HertzU64::MHz(3200);
 const PLL_VCO_FREQ_HIGH: HertzU64 = HertzU64::MHz(2400);
 const PLL_VCO_FREQ_MIN: HertzU64 = HertzU64::MHz(2000);
+/// PLL Parameter register address of each PLL
+pub const PLL_PARAMETER_ADDR: [u8; 4] = [0x08, 0x60, 0x64, 0x68];
+
+/// # PLL Role
+///
+/// What a chip PLL is used for, see `pll_roles()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum PllRole {
+    /// Clocks the hashing cores
+    Hash,
+    /// Can clock the UART for high baudrates
+    Uart,
+    /// Present, but its use is unknown
+    Auxiliary,
+    /// Not used
+    Unused,
+}
+
+/// ## Get the role of each PLL of a chip
+///
+/// Every PLL is `Auxiliary` except the `hash_pll_id` and `uart_pll_id` ones.
+/// Used to build the per-chip role tables.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::pll::{pll_roles, PllRole};
+///
+/// assert_eq!(pll_roles::<2>(0, 1), [PllRole::Hash, PllRole::Uart]);
+/// assert_eq!(
+///     pll_roles::<4>(0, 3),
+///     [PllRole::Hash, PllRole::Auxiliary, PllRole::Auxiliary, PllRole::Uart]
+/// );
+/// ```
+pub const fn pll_roles<const N: usize>(hash_pll_id: usize, uart_pll_id: usize) -> [PllRole; N] {
+    let mut roles = [PllRole::Auxiliary; N];
+    roles[hash_pll_id] = PllRole::Hash;
+    roles[uart_pll_id] = PllRole::Uart;
+    roles
+}
+
+/// ## Get the Frequency of a chip PLL output
+///
+/// Same as `Pll::frequency()` for the PLL `pll_id` of a chip `plls`, using the chip input clock.
+/// Returns 0 for an unknown PLL.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::pll::{pll_frequency, Pll};
+/// use fugit::HertzU64;
+///
+/// let clki = HertzU64::MHz(25);
+/// let mut plls = [Pll::default(); 2];
+/// plls[0].set_frequency(clki, 0, HertzU64::MHz(400), true);
+/// assert_eq!(pll_frequency(&plls, clki, 0, 0), plls[0].frequency(clki, 0));
+/// assert_eq!(pll_frequency(&plls, clki, 0, 0), HertzU64::MHz(400));
+/// assert_eq!(pll_frequency(&plls, clki, 2, 0), HertzU64::MHz(0)); // unknown PLL
+/// ```
+pub fn pll_frequency(plls: &[Pll], in_clk_freq: HertzU64, pll_id: usize, out: usize) -> HertzU64 {
+    plls.get(pll_id)
+        .map_or(HertzU64::MHz(0), |pll| pll.frequency(in_clk_freq, out))
+}
+
+/// # PLL Dividers
+///
+/// Set of dividers realizing a given frequency on one PLL output, see `Pll::solve_for_freq()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PllDividers {
+    pub fb_div: u16,
+    pub ref_div: u8,
+    pub post1_div: u8,
+    pub post2_div: u8,
+    pub out_div: u8,
+}
+
+impl PllDividers {
+    /// ## Check the dividers against the PLL constraints
+    ///
+    /// Each divider must fit in its field, FB and REF dividers can not be null,
+    /// POSTDIV1 must be greater or equal to POSTDIV2, and the resulting VCO frequency
+    /// must be in the PLL range.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::{pll::PllDividers, Error};
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let mut div = PllDividers { fb_div: 96, ref_div: 1, post1_div: 4, post2_div: 0, out_div: 4 };
+    /// assert_eq!(div.check(clki), Ok(()));
+    /// div.post2_div = 5; // POSTDIV2 > POSTDIV1
+    /// assert_eq!(div.check(clki), Err(Error::InvalidPllDividers { dividers: div }));
+    /// div.post2_div = 0;
+    /// div.fb_div = 200; // VCO at 5 GHz
+    /// assert_eq!(div.check(clki), Err(Error::InvalidPllDividers { dividers: div }));
+    /// ```
+    pub fn check(&self, in_clk_freq: HertzU64) -> crate::Result<()> {
+        let vco_freq = in_clk_freq * self.fb_div as u32 / self.ref_div.max(1) as u32;
+        if self.fb_div == 0
+            || self.fb_div as u32 > Pll::FBDIV_MASK
+            || self.ref_div == 0
+            || self.ref_div as u32 > Pll::REFDIV_MASK
+            || self.post1_div as u32 > Pll::POSTDIV1_MASK
+            || self.post2_div > self.post1_div
+            || self.out_div > 0xf
+            || (self.ref_div == 1 && vco_freq > HertzU64::MHz(3125))
+            || vco_freq > PLL_VCO_FREQ_MAX
+            || vco_freq <= PLL_VCO_FREQ_MIN
+        {
+            return Err(crate::Error::InvalidPllDividers { dividers: *self });
+        }
+        Ok(())
+    }
+}
 
 #[derive(Debug, Clone, Copy, Default)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -68,6 +188,54 @@ impl Pll {
         self
     }
 
+    /// ## Read PLL Parameter command.
+    ///
+    /// Build the command reading back the Parameter register of a PLL, to poll its lock status.
+    /// Return `None` if the PLL does not exist.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::pll::Pll;
+    /// use bm13xx_protocol::command::Destination;
+    ///
+    /// assert_eq!(Pll::read_parameter_cmd(0, Destination::All), Some([0x55, 0xAA, 0x52, 0x05, 0x00, 0x08, 0x07]));
+    /// assert_eq!(Pll::read_parameter_cmd(3, Destination::Chip(2)), Some([0x55, 0xAA, 0x42, 0x05, 0x02, 0x68, 0x08]));
+    /// assert_eq!(Pll::read_parameter_cmd(4, Destination::All), None);
+    /// ```
+    pub fn read_parameter_cmd(pll_id: usize, dest: Destination) -> Option<[u8; 7]> {
+        PLL_PARAMETER_ADDR
+            .get(pll_id)
+            .map(|reg_addr| Command::read_reg(*reg_addr, dest))
+    }
+
+    /// ## Parse the PLL lock status from a register response.
+    ///
+    /// Return the PLL ID and its lock status if the response is a PLL Parameter register.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::pll::Pll;
+    /// use bm13xx_protocol::response::RegisterResponse;
+    ///
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x08, reg_value: 0xC054_0165 };
+    /// assert_eq!(Pll::lock_status(&resp), Some((0, true)));
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x60, reg_value: 0x2050_0174 };
+    /// assert_eq!(Pll::lock_status(&resp), Some((1, false)));
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x00, reg_value: 0x1370_0000 };
+    /// assert_eq!(Pll::lock_status(&resp), None);
+    /// ```
+    pub fn lock_status(resp: &RegisterResponse) -> Option<(usize, bool)> {
+        PLL_PARAMETER_ADDR
+            .iter()
+            .position(|reg_addr| *reg_addr == resp.reg_addr)
+            .map(|pll_id| {
+                (
+                    pll_id,
+                    (resp.reg_value >> Self::LOCKED_OFFSET) & Self::LOCKED_MASK != 0,
+                )
+            })
+    }
+
     /// ## Set the PLL Divider.
     ///
     /// ### Example
@@ -120,6 +288,9 @@ impl Pll {
 
     /// ## Get the PLL VCO Frequency.
     ///
+    /// The computation is done on 128 bits and saturates, so any parameter is safe.
+    /// A null `ref_div` gives a null frequency.
+    ///
     /// ### Example
     /// ```
     /// use fugit::HertzU64;
@@ -130,10 +301,17 @@ impl Pll {
     /// assert_eq!(pll.vco_freq(HertzU64::MHz(25)), HertzU64::MHz(2400));
     /// pll.set_parameter(0x0064_0111); // BM1397 PLL1 default value
     /// assert_eq!(pll.vco_freq(HertzU64::MHz(25)), HertzU64::MHz(0));
+    /// pll.set_parameter(0xCFFF_0100); // maximum fb_div
+    /// assert_eq!(pll.vco_freq(HertzU64::MHz(25)), HertzU64::MHz(102_375));
+    /// assert_eq!(pll.frequency(HertzU64::MHz(25), 0), HertzU64::MHz(102_375));
+    /// assert_eq!(pll.vco_freq(HertzU64::from_raw(u64::MAX)), HertzU64::from_raw(u64::MAX));
+    /// pll.set_parameter(0xC060_0061); // null ref_div
+    /// assert_eq!(pll.vco_freq(HertzU64::MHz(25)), HertzU64::MHz(0));
     /// ```
     pub fn vco_freq(&self, in_clk_freq: HertzU64) -> HertzU64 {
-        if self.enabled && self.locked {
-            in_clk_freq * (self.fb_div as u32) / (self.ref_div as u32)
+        if self.enabled && self.locked && self.ref_div != 0 {
+            let vco_freq = in_clk_freq.raw() as u128 * self.fb_div as u128 / self.ref_div as u128;
+            HertzU64::from_raw(vco_freq.min(u64::MAX as u128) as u64)
         } else {
             HertzU64::MHz(0)
         }
@@ -141,6 +319,9 @@ impl Pll {
 
     /// ## Get the PLL Frequency for a given output.
     ///
+    /// The frequency is truncated to the Hz, both on the VCO frequency and on the output division,
+    /// see `frequency_exact()` for the exact value.
+    ///
     /// ### Example
     /// ```
     /// use fugit::HertzU64;
@@ -167,6 +348,38 @@ impl Pll {
             HertzU64::MHz(0)
         }
     }
+
+    /// ## Get the exact PLL Frequency for a given output.
+    ///
+    /// Same as `frequency()`, but computed in floating point, without truncation.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let mut pll = Pll::default();
+    /// pll.set_parameter(0xC060_0161); // BM1397 PLL0 default value
+    /// pll.set_divider(0x0304_0607); // BM1397 PLL0 default divider
+    /// assert_eq!(pll.frequency(clki, 0), HertzU64::Hz(21_428_571));
+    /// assert!((pll.frequency_exact(clki, 0) - 21_428_571.428).abs() < 0.001);
+    /// assert_eq!(pll.frequency_exact(clki, 5), 0.0);
+    /// pll.set_frequency(clki, 0, HertzU64::MHz(425), false);
+    /// assert_eq!(pll.frequency_exact(clki, 0), 425_000_000.0); // divides evenly
+    /// ```
+    pub fn frequency_exact(&self, in_clk_freq: HertzU64, out: usize) -> f64 {
+        if out < PLL_OUT_MAX && self.enabled && self.locked && self.ref_div != 0 {
+            in_clk_freq.raw() as f64 * self.fb_div as f64
+                / (self.ref_div as f64
+                    * (self.post1_div as f64 + 1.0)
+                    * (self.post2_div as f64 + 1.0)
+                    * (self.out_div[out] as f64 + 1.0))
+        } else {
+            0.0
+        }
+    }
+
     pub fn set_frequency(
         &mut self,
         in_clk_freq: HertzU64,
@@ -196,7 +409,7 @@ impl Pll {
                                 pll.lock();
                             }
                             let vco_freq = pll.vco_freq(in_clk_freq);
-                            pll.vco_high_freq = vco_freq > PLL_VCO_FREQ_HIGH;
+                            pll.vco_high_freq = vco_freq >= PLL_VCO_FREQ_HIGH;
                             if (pll.ref_div > 1 || vco_freq <= HertzU64::MHz(3125))
                                 && (vco_freq <= PLL_VCO_FREQ_MAX)
                                 && (vco_freq > PLL_VCO_FREQ_MIN)
@@ -219,6 +432,196 @@ impl Pll {
         self
     }
 
+    /// ## Check if a frequency needs the high VCO range.
+    ///
+    /// Tell if `set_frequency()` would select a VCO frequency of 2400 MHz or above to realize
+    /// `target_freq` on the given output, setting the VCO high frequency bit (PLL Parameter MSB 0x50
+    /// instead of 0x40). Returns `None` if `target_freq` can not be realized.
+    /// The PLL itself is left untouched.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let pll = Pll::default();
+    /// assert_eq!(pll.needs_high_vco(clki, 0, HertzU64::MHz(475)), Some(false)); // VCO at 2375 MHz
+    /// assert_eq!(pll.needs_high_vco(clki, 0, HertzU64::MHz(480)), Some(true)); // VCO at 2400 MHz
+    /// assert_eq!(pll.needs_high_vco(clki, 0, HertzU64::MHz(485)), Some(true)); // VCO at 2425 MHz
+    /// assert_eq!(pll.needs_high_vco(clki, 0, HertzU64::MHz(5000)), None);
+    /// assert_eq!(pll.needs_high_vco(clki, 6, HertzU64::MHz(480)), None); // no such output
+    /// assert!(!pll.vco_high_freq());
+    /// ```
+    pub fn needs_high_vco(
+        &self,
+        in_clk_freq: HertzU64,
+        out: usize,
+        target_freq: HertzU64,
+    ) -> Option<bool> {
+        let mut pll = *self;
+        // lock the copy so its VCO frequency is evaluated even if this PLL is not locked yet
+        pll.set_frequency(in_clk_freq, out, target_freq, true);
+        let freq = pll.frequency(in_clk_freq, out);
+        let freq_diff = if target_freq > freq {
+            target_freq - freq
+        } else {
+            freq - target_freq
+        };
+        (freq_diff < HertzU64::MHz(1)).then_some(pll.vco_high_freq())
+    }
+
+    /// ## Get the frequencies the PLL can realize for a given output.
+    ///
+    /// Enumerate the dividers explored by `set_frequency()` and return the resulting frequencies
+    /// between `min_freq` and `max_freq` (inclusive), sorted and deduplicated.
+    /// Only the `N` lowest frequencies are kept.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let pll = Pll::default();
+    /// let freqs = pll.achievable_freqs::<4>(HertzU64::MHz(25), 0, HertzU64::MHz(100), HertzU64::MHz(110));
+    /// assert_eq!(
+    ///     freqs,
+    ///     [
+    ///         HertzU64::MHz(100),
+    ///         HertzU64::Hz(100_416_666),
+    ///         HertzU64::Hz(100_446_428),
+    ///         HertzU64::kHz(100_500),
+    ///     ]
+    /// );
+    /// let freqs = pll.achievable_freqs::<256>(HertzU64::MHz(25), 0, HertzU64::MHz(100), HertzU64::MHz(110));
+    /// assert_eq!(freqs.last(), Some(&HertzU64::MHz(110)));
+    /// assert!(freqs.windows(2).all(|f| f[0] < f[1]));
+    /// assert!(pll.achievable_freqs::<4>(HertzU64::MHz(25), 5, HertzU64::MHz(100), HertzU64::MHz(110)).is_empty()); // output out of bound
+    /// ```
+    pub fn achievable_freqs<const N: usize>(
+        &self,
+        in_clk_freq: HertzU64,
+        out: usize,
+        min_freq: HertzU64,
+        max_freq: HertzU64,
+    ) -> Vec<HertzU64, N> {
+        let mut freqs = Vec::<HertzU64, N>::new();
+        if out >= PLL_OUT_MAX {
+            return freqs;
+        }
+        let mut pll = *self;
+        pll.out_div[out] = 0;
+        pll.enable().lock();
+        for ref_div in 1..=2 {
+            pll.ref_div = ref_div;
+            for post2_div in 0..=7 {
+                pll.post2_div = post2_div;
+                for post1_div in post2_div..=7 {
+                    pll.post1_div = post1_div;
+                    for fb_div in 0..251 {
+                        pll.fb_div = fb_div;
+                        let vco_freq = pll.vco_freq(in_clk_freq);
+                        if (pll.ref_div == 1 && vco_freq > HertzU64::MHz(3125))
+                            || vco_freq > PLL_VCO_FREQ_MAX
+                            || vco_freq <= PLL_VCO_FREQ_MIN
+                        {
+                            continue;
+                        }
+                        let freq = pll.frequency(in_clk_freq, out);
+                        if freq < min_freq || freq > max_freq {
+                            continue;
+                        }
+                        let pos = match freqs.binary_search(&freq) {
+                            Ok(_) => continue,
+                            Err(pos) => pos,
+                        };
+                        if pos == N {
+                            continue;
+                        }
+                        if freqs.is_full() {
+                            freqs.pop();
+                        }
+                        freqs.insert(pos, freq).ok();
+                    }
+                }
+            }
+        }
+        freqs
+    }
+
+    /// ## Find the PLL dividers realizing exactly a target frequency on a given output.
+    ///
+    /// The dividers are searched with the same VCO constraints as `set_frequency()`,
+    /// also iterating over the output divider. Returns `None` if the target frequency
+    /// cannot be exactly reached.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::{Pll, PllDividers};
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// // UART clock for 6 Mbaud with BT8D=7: 2 * 6_000_000 * (7 + 1)
+    /// let div = Pll::solve_for_freq(clki, HertzU64::MHz(96), 4).unwrap();
+    /// assert_eq!(div, PllDividers { fb_div: 96, ref_div: 1, post1_div: 4, post2_div: 0, out_div: 4 });
+    /// let mut pll = Pll::default();
+    /// pll.enable().lock().set_dividers(4, &div);
+    /// assert_eq!(pll.frequency(clki, 4), HertzU64::MHz(96));
+    /// assert_eq!(Pll::solve_for_freq(clki, HertzU64::Hz(96_000_001), 4), None);
+    /// assert_eq!(Pll::solve_for_freq(clki, HertzU64::MHz(96), 5), None); // output out of bound
+    /// ```
+    pub fn solve_for_freq(
+        in_clk_freq: HertzU64,
+        target_freq: HertzU64,
+        out: usize,
+    ) -> Option<PllDividers> {
+        if out >= PLL_OUT_MAX || in_clk_freq.raw() == 0 {
+            return None;
+        }
+        for ref_div in 1..=2u8 {
+            for post2_div in 0..=7u8 {
+                for post1_div in post2_div..=7 {
+                    for out_div in 0..=0xfu8 {
+                        let div =
+                            (post1_div as u64 + 1) * (post2_div as u64 + 1) * (out_div as u64 + 1);
+                        let vco_freq = target_freq * div as u32;
+                        let fb_clk = vco_freq.raw() * ref_div as u64;
+                        if fb_clk % in_clk_freq.raw() != 0 {
+                            continue;
+                        }
+                        let fb_div = fb_clk / in_clk_freq.raw();
+                        if fb_div > Self::FBDIV_MASK as u64
+                            || (ref_div == 1 && vco_freq > HertzU64::MHz(3125))
+                            || vco_freq > PLL_VCO_FREQ_MAX
+                            || vco_freq <= PLL_VCO_FREQ_MIN
+                        {
+                            continue;
+                        }
+                        return Some(PllDividers {
+                            fb_div: fb_div as u16,
+                            ref_div,
+                            post1_div,
+                            post2_div,
+                            out_div,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// ## Set the PLL dividers for a given output.
+    ///
+    /// See `solve_for_freq()`.
+    pub fn set_dividers(&mut self, out: usize, dividers: &PllDividers) -> &mut Self {
+        self.set_fb_div(dividers.fb_div)
+            .set_ref_div(dividers.ref_div)
+            .set_post1_div(dividers.post1_div)
+            .set_post2_div(dividers.post2_div)
+            .set_out_div(out, dividers.out_div)
+    }
+
     /// ## Handle the PLL locked field.
     ///
     /// ### Example
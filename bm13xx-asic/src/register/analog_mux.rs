@@ -1,5 +1,42 @@
 use crate::register::Register;
 
+/// Analog Mux Channel.
+///
+/// Diagnostic channel selected by the `DIODE_VDD_MUX_SEL` field of [`AnalogMuxControlV2`].
+/// The BM1366 and BM1370 init sequences select `CH3`.
+///
+/// This is used by [`AnalogMuxControlV2::channel`] method.
+///
+/// [`AnalogMuxControlV2`]: crate::register::AnalogMuxControlV2
+/// [`AnalogMuxControlV2::channel`]: crate::register::AnalogMuxControlV2::channel
+#[derive(Copy, Clone, Eq, PartialEq, Debug, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[repr(u8)]
+pub enum AnalogMuxChannel {
+    CH0 = 0,
+    CH1 = 1,
+    CH2 = 2,
+    CH3 = 3,
+    CH4 = 4,
+    CH5 = 5,
+    CH6 = 6,
+    CH7 = 7,
+    CH8 = 8,
+    CH9 = 9,
+    CH10 = 10,
+    CH11 = 11,
+    CH12 = 12,
+    CH13 = 13,
+    CH14 = 14,
+    CH15 = 15,
+}
+
+impl From<AnalogMuxChannel> for u8 {
+    fn from(val: AnalogMuxChannel) -> u8 {
+        val as u8
+    }
+}
+
 /// # Analog Mux Control register
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct AnalogMuxControl(pub u32);
@@ -91,6 +128,40 @@ impl AnalogMuxControlV2 {
             ((mux_sel as u32) & Self::DIODE_VDD_MUX_SEL_MASK) << Self::DIODE_VDD_MUX_SEL_OFFSET;
         self
     }
+
+    /// ## Get the selected Analog Mux Channel.
+    ///
+    /// This returns an `AnalogMuxChannel` decoded from the `DIODE_VDD_MUX_SEL` value.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::register::{AnalogMuxChannel, AnalogMuxControlV2};
+    ///
+    /// assert_eq!(AnalogMuxControlV2(0x0000_0000).channel(), AnalogMuxChannel::CH0); // BM1366 default value
+    /// assert_eq!(AnalogMuxControlV2(0x0000_0003).channel(), AnalogMuxChannel::CH3); // BM1366 init() value
+    /// assert_eq!(AnalogMuxControlV2(0x0000_000f).channel(), AnalogMuxChannel::CH15);
+    /// assert_eq!(u8::from(AnalogMuxChannel::CH3), 3);
+    /// ```
+    pub const fn channel(&self) -> AnalogMuxChannel {
+        match self.diode_vdd_mux_sel() {
+            0 => AnalogMuxChannel::CH0,
+            1 => AnalogMuxChannel::CH1,
+            2 => AnalogMuxChannel::CH2,
+            3 => AnalogMuxChannel::CH3,
+            4 => AnalogMuxChannel::CH4,
+            5 => AnalogMuxChannel::CH5,
+            6 => AnalogMuxChannel::CH6,
+            7 => AnalogMuxChannel::CH7,
+            8 => AnalogMuxChannel::CH8,
+            9 => AnalogMuxChannel::CH9,
+            10 => AnalogMuxChannel::CH10,
+            11 => AnalogMuxChannel::CH11,
+            12 => AnalogMuxChannel::CH12,
+            13 => AnalogMuxChannel::CH13,
+            14 => AnalogMuxChannel::CH14,
+            _ => AnalogMuxChannel::CH15,
+        }
+    }
 }
 
 impl core::fmt::Display for AnalogMuxControlV2 {
@@ -1,6 +1,24 @@
 use crate::register::Register;
+use fugit::HertzU64;
 
 /// # Ordered Clock Monitor register
+///
+/// Used to measure the frequency of one of the chip clocks.
+/// Writing START with a CLK_SEL starts a measurement, then CLK_COUNT holds the number of cycles
+/// of the monitored clock counted during the measurement window.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{OrderedClockMonitor, Register};
+///
+/// let mut clk_mon = OrderedClockMonitor(0x0001_0200); // BM1366/BM1370 default value
+/// assert!(!clk_mon.started());
+/// assert_eq!(clk_mon.clk_sel(), 0);
+/// assert_eq!(clk_mon.clk_count(), 0x0200);
+/// clk_mon.start().set_clk_sel(3);
+/// assert_eq!(clk_mon.val(), 0x8301_0200);
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct OrderedClockMonitor(pub u32);
 impl_boilerplate_for!(OrderedClockMonitor);
@@ -8,25 +26,124 @@ impl_boilerplate_for!(OrderedClockMonitor);
 impl OrderedClockMonitor {
     pub const ADDR: u8 = 0x6C;
 
-    // const START_OFFSET: u8 = 31;
-    // const CLK_SEL_OFFSET: u8 = 24;
-    // const CLK_COUNT_OFFSET: u8 = 0;
+    const START_OFFSET: u8 = 31;
+    const CLK_SEL_OFFSET: u8 = 24;
+    const CLK_COUNT_OFFSET: u8 = 0;
+
+    const START_MASK: u32 = 0b1;
+    const CLK_SEL_MASK: u32 = 0b1111;
+    const CLK_COUNT_MASK: u32 = 0xffff;
+
+    /// ## Handle the START field.
+    ///
+    /// Get and set the START state.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::OrderedClockMonitor;
+    ///
+    /// let mut clk_mon = OrderedClockMonitor(0x0001_0200);
+    /// assert!(!clk_mon.started());
+    /// assert!(clk_mon.start().started());
+    /// assert!(!clk_mon.stop().started());
+    /// ```
+    pub const fn started(&self) -> bool {
+        (self.0 >> Self::START_OFFSET) & Self::START_MASK == Self::START_MASK
+    }
+    pub fn start(&mut self) -> &mut Self {
+        self.0 |= Self::START_MASK << Self::START_OFFSET;
+        self
+    }
+    pub fn stop(&mut self) -> &mut Self {
+        self.0 &= !(Self::START_MASK << Self::START_OFFSET);
+        self
+    }
+
+    /// ## Handle the CLK_SEL field.
+    ///
+    /// Get and set the index of the monitored clock.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::OrderedClockMonitor;
+    ///
+    /// let mut clk_mon = OrderedClockMonitor(0x0001_0200);
+    /// assert_eq!(clk_mon.clk_sel(), 0);
+    /// assert_eq!(clk_mon.set_clk_sel(0xf).clk_sel(), 0xf); // max value
+    /// assert_eq!(clk_mon.set_clk_sel(0x10).clk_sel(), 0); // out of bound value
+    /// ```
+    pub const fn clk_sel(&self) -> u8 {
+        ((self.0 >> Self::CLK_SEL_OFFSET) & Self::CLK_SEL_MASK) as u8
+    }
+    pub fn set_clk_sel(&mut self, clk_sel: u8) -> &mut Self {
+        self.0 &= !(Self::CLK_SEL_MASK << Self::CLK_SEL_OFFSET);
+        self.0 |= ((clk_sel as u32) & Self::CLK_SEL_MASK) << Self::CLK_SEL_OFFSET;
+        self
+    }
+
+    /// ## Handle the CLK_COUNT field.
+    ///
+    /// Get the number of cycles of the monitored clock counted during the measurement.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::OrderedClockMonitor;
+    ///
+    /// assert_eq!(OrderedClockMonitor(0x0001_0200).clk_count(), 0x0200);
+    /// assert_eq!(OrderedClockMonitor(0x8301_ffff).clk_count(), 0xffff);
+    /// ```
+    pub const fn clk_count(&self) -> u16 {
+        ((self.0 >> Self::CLK_COUNT_OFFSET) & Self::CLK_COUNT_MASK) as u16
+    }
 
-    // const START_MASK: u32 = 0b1;
-    // const CLK_SEL_MASK: u32 = 0b1111;
-    // const CLK_COUNT_MASK: u32 = 0xffff;
+    /// ## Get the measured frequency of the monitored clock.
+    ///
+    /// The CLK_COUNT cycles of the monitored clock are counted during `ref_cycles` cycles of the
+    /// reference clock running at `ref_freq` (usually the Hash frequency).
+    /// Returns 0 Hz if `ref_cycles` is 0.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::OrderedClockMonitor;
+    /// use fugit::HertzU64;
+    ///
+    /// let clk_mon = OrderedClockMonitor(0x0301_0200);
+    /// assert_eq!(clk_mon.measured_freq(HertzU64::MHz(500), 1024), HertzU64::MHz(250));
+    /// assert_eq!(clk_mon.measured_freq(HertzU64::MHz(500), 512), HertzU64::MHz(500));
+    /// assert_eq!(clk_mon.measured_freq(HertzU64::MHz(500), 0), HertzU64::MHz(0));
+    /// ```
+    pub fn measured_freq(&self, ref_freq: HertzU64, ref_cycles: u32) -> HertzU64 {
+        if ref_cycles == 0 {
+            return HertzU64::MHz(0);
+        }
+        HertzU64::Hz(ref_freq.raw() * self.clk_count() as u64 / ref_cycles as u64)
+    }
 }
 
 impl core::fmt::Display for OrderedClockMonitor {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("OrderedClockMonitor").finish()
+        f.debug_struct("OrderedClockMonitor")
+            .field("start", &self.started())
+            .field("clk_sel", &self.clk_sel())
+            .field("clk_count", &self.clk_count())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for OrderedClockMonitor {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "OrderedClockMonitor {{  }}",);
+        defmt::write!(
+            fmt,
+            "OrderedClockMonitor {{ start: {}, clk_sel: {}, clk_count: {} }}",
+            self.started(),
+            self.clk_sel(),
+            self.clk_count(),
+        );
     }
 }
 
@@ -31,9 +31,22 @@ impl CoreRegisterControl {
     /// assert_eq!(CoreRegisterControl::read_core_reg(0, ClockDelayCtrl(0x74)), 0x8000_00ff);
     /// ```
     pub fn read_core_reg(core_id: u8, core_reg: impl CoreRegister) -> u32 {
+        Self::read_core_reg_id(core_id, core_reg.id())
+    }
+    /// ## Set CoreRegisterControl for a Core Register Read by ID.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::CoreRegisterControl;
+    /// use bm13xx_asic::core_register::ClockDelayCtrl;
+    ///
+    /// assert_eq!(CoreRegisterControl::read_core_reg_id(0, ClockDelayCtrl::ID), 0x8000_00ff);
+    /// ```
+    pub const fn read_core_reg_id(core_id: u8, core_reg_id: u8) -> u32 {
         (Self::DO_CMD_MASK << Self::DO_CMD_OFFSET)
             | (((core_id as u32) & Self::CORE_ID_MASK) << Self::CORE_ID_OFFSET)
-            | (((core_reg.id() as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
+            | (((core_reg_id as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
             | Self::CORE_REG_VAL_MASK
     }
     /// ## Set CoreRegisterControl for a Core Register Write.
@@ -46,11 +59,24 @@ impl CoreRegisterControl {
     ///
     /// assert_eq!(CoreRegisterControl::write_core_reg(0, ClockDelayCtrl(0x74)), 0x8000_8074);
     pub fn write_core_reg(core_id: u8, core_reg: impl CoreRegister) -> u32 {
+        Self::write_core_reg_id(core_id, core_reg.id(), core_reg.val())
+    }
+    /// ## Set CoreRegisterControl for a Core Register Write by ID.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::CoreRegisterControl;
+    /// use bm13xx_asic::core_register::ClockDelayCtrl;
+    ///
+    /// assert_eq!(CoreRegisterControl::write_core_reg_id(0, ClockDelayCtrl::ID, 0x74), 0x8000_8074);
+    /// ```
+    pub const fn write_core_reg_id(core_id: u8, core_reg_id: u8, core_reg_val: u8) -> u32 {
         (Self::DO_CMD_MASK << Self::DO_CMD_OFFSET)
             | (Self::RD_WR_MASK << Self::RD_WR_OFFSET)
             | (((core_id as u32) & Self::CORE_ID_MASK) << Self::CORE_ID_OFFSET)
-            | (((core_reg.id() as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
-            | (((core_reg.val() as u32) & Self::CORE_REG_VAL_MASK) << Self::CORE_REG_VAL_OFFSET)
+            | (((core_reg_id as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
+            | (((core_reg_val as u32) & Self::CORE_REG_VAL_MASK) << Self::CORE_REG_VAL_OFFSET)
     }
 }
 
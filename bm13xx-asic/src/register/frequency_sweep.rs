@@ -1,4 +1,8 @@
 use crate::register::Register;
+use bm13xx_protocol::{
+    command::{Command, Destination},
+    response::RegisterResponse,
+};
 
 /// # Frequency Sweep Control 1 register
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -34,20 +38,78 @@ impl_boilerplate_for!(GoldenNonceForSweepReturn);
 impl GoldenNonceForSweepReturn {
     pub const ADDR: u8 = 0x94;
 
-    // const GNOSWR_OFFSET: u8 = 0;
+    const GNOSWR_OFFSET: u8 = 0;
 
-    // const GNOSWR_MASK: u32 = 0xffff_ffff;
+    const GNOSWR_MASK: u32 = 0xffff_ffff;
+
+    /// ## Handle the GNOSWR field.
+    ///
+    /// Get the Golden Nonce found during the last frequency sweep.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::register::GoldenNonceForSweepReturn;
+    ///
+    /// let gnoswr = GoldenNonceForSweepReturn(0x0037_6400); // BM1397 default value
+    /// assert_eq!(gnoswr.gnoswr(), 0x0037_6400);
+    /// ```
+    pub const fn gnoswr(&self) -> u32 {
+        (self.0 >> Self::GNOSWR_OFFSET) & Self::GNOSWR_MASK
+    }
+
+    /// ## Build the Read Register command of the Golden Nonce.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::register::GoldenNonceForSweepReturn;
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// assert_eq!(
+    ///     GoldenNonceForSweepReturn::read_cmd(Destination::Chip(4)),
+    ///     Command::read_reg(0x94, Destination::Chip(4))
+    /// );
+    /// ```
+    pub fn read_cmd(dest: Destination) -> [u8; 7] {
+        Command::read_reg(Self::ADDR, dest)
+    }
+
+    /// ## Parse the Golden Nonce from a register response.
+    ///
+    /// Return the chip address and the Golden Nonce it found during the last frequency sweep,
+    /// or `None` if the response is not a Golden Nonce For Sweep Return register.
+    /// The register does not hold the frequency step the nonce was found at, it has to be
+    /// tracked from the FrequencySweepControl1 programming.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::register::GoldenNonceForSweepReturn;
+    /// use bm13xx_protocol::response::RegisterResponse;
+    ///
+    /// let resp = RegisterResponse { chip_addr: 4, reg_addr: 0x94, reg_value: 0x0037_6400 };
+    /// assert_eq!(GoldenNonceForSweepReturn::parse(&resp), Some((4, 0x0037_6400)));
+    /// let resp = RegisterResponse { chip_addr: 4, reg_addr: 0x90, reg_value: 0x0037_6400 };
+    /// assert_eq!(GoldenNonceForSweepReturn::parse(&resp), None);
+    /// ```
+    pub fn parse(resp: &RegisterResponse) -> Option<(u8, u32)> {
+        (resp.reg_addr == Self::ADDR).then_some((resp.chip_addr, Self(resp.reg_value).gnoswr()))
+    }
 }
 
 impl core::fmt::Display for GoldenNonceForSweepReturn {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("GoldenNonceForSweepReturn").finish()
+        f.debug_struct("GoldenNonceForSweepReturn")
+            .field("gnoswr", &self.gnoswr())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for GoldenNonceForSweepReturn {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "GoldenNonceForSweepReturn {{  }}",);
+        defmt::write!(
+            fmt,
+            "GoldenNonceForSweepReturn {{ gnoswr: {} }}",
+            self.gnoswr(),
+        );
     }
 }
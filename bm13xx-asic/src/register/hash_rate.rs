@@ -8,22 +8,74 @@ impl_boilerplate_for!(HashRate);
 impl HashRate {
     pub const ADDR: u8 = 0x04;
 
-    // const LONG_OFFSET: u8 = 31;
-    // const HASHRATE_OFFSET: u8 = 0;
+    const LONG_OFFSET: u8 = 31;
+    const HASHRATE_OFFSET: u8 = 0;
 
-    // const LONG_MASK: u32 = 0b1;
-    // const HASHRATE_MASK: u32 = 0x7fff_ffff;
+    const LONG_MASK: u32 = 0b1;
+    const HASHRATE_MASK: u32 = 0x7fff_ffff;
+
+    /// ## Handle the LONG field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::HashRate;
+    ///
+    /// assert!(HashRate(0x8000_0000).long()); // BM1397 default value
+    /// assert!(!HashRate(0x0001_2a89).long()); // BM1366 default value
+    /// ```
+    pub const fn long(&self) -> bool {
+        (self.0 >> Self::LONG_OFFSET) & Self::LONG_MASK == Self::LONG_MASK
+    }
+
+    /// ## Handle the HASHRATE field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::HashRate;
+    ///
+    /// assert_eq!(HashRate(0x8000_0000).hashrate(), 0); // BM1397 default value
+    /// assert_eq!(HashRate(0x0001_2a89).hashrate(), 0x0001_2a89); // BM1366 default value
+    /// ```
+    pub const fn hashrate(&self) -> u32 {
+        (self.0 >> Self::HASHRATE_OFFSET) & Self::HASHRATE_MASK
+    }
+
+    /// ## Get the measured Hashrate in GH/s.
+    ///
+    /// The HASHRATE field counts units of 2^24 hashes per second.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::HashRate;
+    ///
+    /// assert_eq!(HashRate(0x8000_0000).hashrate_ghs(), 0.0); // BM1397 default value
+    /// assert_eq!(HashRate(0x0000_0100).hashrate_ghs(), 4.294_967_3);
+    /// ```
+    pub fn hashrate_ghs(&self) -> f32 {
+        self.hashrate() as f32 * (1u32 << 24) as f32 / 1_000_000_000.0
+    }
 }
 
 impl core::fmt::Display for HashRate {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("HashRate").finish()
+        f.debug_struct("HashRate")
+            .field("long", &self.long())
+            .field("hashrate", &self.hashrate())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for HashRate {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "HashRate {{  }}",);
+        defmt::write!(
+            fmt,
+            "HashRate {{ long: {}, hashrate: {} }}",
+            self.long(),
+            self.hashrate(),
+        );
     }
 }
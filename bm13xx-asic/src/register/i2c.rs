@@ -1,6 +1,14 @@
 use crate::register::Register;
+use bm13xx_protocol::{
+    command::{Command, Destination},
+    response::RegisterResponse,
+};
 
 /// # I2C Control register
+///
+/// Drive the chip I2C master, used to reach the hashboard temperature sensor.
+/// A transaction is started by setting DO_CMD, the chip clears BUSY once done and,
+/// for a read, returns the I2C register value in I2C_REG_VAL.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct I2CControl(pub u32);
 impl_boilerplate_for!(I2CControl);
@@ -8,30 +16,235 @@ impl_boilerplate_for!(I2CControl);
 impl I2CControl {
     pub const ADDR: u8 = 0x1C;
 
-    // const BUSY_OFFSET: u8 = 31;
-    // const DO_CMD_OFFSET: u8 = 24;
-    // const I2C_ADDR_OFFSET: u8 = 17;
-    // const RD_WR_OFFSET: u8 = 16;
-    // const I2C_REG_ADDR_OFFSET: u8 = 8;
-    // const I2C_REG_VAL_OFFSET: u8 = 0;
-
-    // const BUSY_MASK: u32 = 0b1;
-    // const DO_CMD_MASK: u32 = 0b1;
-    // const I2C_ADDR_MASK: u32 = 0x7f;
-    // const RD_WR_MASK: u32 = 0b1;
-    // const I2C_REG_ADDR_MASK: u32 = 0xff;
-    // const I2C_REG_VAL_MASK: u32 = 0xff;
+    const BUSY_OFFSET: u8 = 31;
+    const DO_CMD_OFFSET: u8 = 24;
+    const I2C_ADDR_OFFSET: u8 = 17;
+    const RD_WR_OFFSET: u8 = 16;
+    const I2C_REG_ADDR_OFFSET: u8 = 8;
+    const I2C_REG_VAL_OFFSET: u8 = 0;
+
+    const BUSY_MASK: u32 = 0b1;
+    const DO_CMD_MASK: u32 = 0b1;
+    const I2C_ADDR_MASK: u32 = 0x7f;
+    const RD_WR_MASK: u32 = 0b1;
+    const I2C_REG_ADDR_MASK: u32 = 0xff;
+    const I2C_REG_VAL_MASK: u32 = 0xff;
+
+    /// ## Handle the BUSY field.
+    ///
+    /// Get the I2C transaction busy state.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    ///
+    /// assert!(!I2CControl(0x0000_0000).is_busy()); // BM1366 default value
+    /// assert!(I2CControl(0x8000_0000).is_busy());
+    /// ```
+    pub const fn is_busy(&self) -> bool {
+        (self.0 >> Self::BUSY_OFFSET) & Self::BUSY_MASK == Self::BUSY_MASK
+    }
+
+    /// ## Handle the DO_CMD field.
+    ///
+    /// Get and set the DO_CMD state, starting an I2C transaction.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    ///
+    /// let mut i2c = I2CControl(0x0100_0000); // BM1397 default value
+    /// assert!(i2c.is_do_cmd());
+    /// assert!(!i2c.clr_do_cmd().is_do_cmd());
+    /// assert!(i2c.set_do_cmd().is_do_cmd());
+    /// ```
+    pub const fn is_do_cmd(&self) -> bool {
+        (self.0 >> Self::DO_CMD_OFFSET) & Self::DO_CMD_MASK == Self::DO_CMD_MASK
+    }
+    pub fn set_do_cmd(&mut self) -> &mut Self {
+        self.0 |= Self::DO_CMD_MASK << Self::DO_CMD_OFFSET;
+        self
+    }
+    pub fn clr_do_cmd(&mut self) -> &mut Self {
+        self.0 &= !(Self::DO_CMD_MASK << Self::DO_CMD_OFFSET);
+        self
+    }
+
+    /// ## Handle the I2C_ADDR field.
+    ///
+    /// Get and set the 7 bits I2C device address.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    ///
+    /// let mut i2c = I2CControl(0x0000_0000);
+    /// assert_eq!(i2c.i2c_addr(), 0x00);
+    /// assert_eq!(i2c.set_i2c_addr(0x4c).i2c_addr(), 0x4c);
+    /// assert_eq!(i2c.set_i2c_addr(0x7f).i2c_addr(), 0x7f); // max value
+    /// assert_eq!(i2c.set_i2c_addr(0x80).i2c_addr(), 0x00); // out of bound value
+    /// ```
+    pub const fn i2c_addr(&self) -> u8 {
+        ((self.0 >> Self::I2C_ADDR_OFFSET) & Self::I2C_ADDR_MASK) as u8
+    }
+    pub fn set_i2c_addr(&mut self, i2c_addr: u8) -> &mut Self {
+        self.0 &= !(Self::I2C_ADDR_MASK << Self::I2C_ADDR_OFFSET);
+        self.0 |= ((i2c_addr as u32) & Self::I2C_ADDR_MASK) << Self::I2C_ADDR_OFFSET;
+        self
+    }
+
+    /// ## Handle the RD_WR field.
+    ///
+    /// Get and set the I2C transaction direction, following the I2C R/W bit convention
+    /// (set for a read).
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    ///
+    /// let mut i2c = I2CControl(0x0000_0000);
+    /// assert!(!i2c.is_read());
+    /// assert!(i2c.set_read().is_read());
+    /// assert!(!i2c.set_write().is_read());
+    /// ```
+    pub const fn is_read(&self) -> bool {
+        (self.0 >> Self::RD_WR_OFFSET) & Self::RD_WR_MASK == Self::RD_WR_MASK
+    }
+    pub fn set_read(&mut self) -> &mut Self {
+        self.0 |= Self::RD_WR_MASK << Self::RD_WR_OFFSET;
+        self
+    }
+    pub fn set_write(&mut self) -> &mut Self {
+        self.0 &= !(Self::RD_WR_MASK << Self::RD_WR_OFFSET);
+        self
+    }
+
+    /// ## Handle the I2C_REG_ADDR field.
+    ///
+    /// Get and set the I2C device register address.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    ///
+    /// let mut i2c = I2CControl(0x0000_0000);
+    /// assert_eq!(i2c.i2c_reg_addr(), 0x00);
+    /// assert_eq!(i2c.set_i2c_reg_addr(0xff).i2c_reg_addr(), 0xff); // max value
+    /// ```
+    pub const fn i2c_reg_addr(&self) -> u8 {
+        ((self.0 >> Self::I2C_REG_ADDR_OFFSET) & Self::I2C_REG_ADDR_MASK) as u8
+    }
+    pub fn set_i2c_reg_addr(&mut self, i2c_reg_addr: u8) -> &mut Self {
+        self.0 &= !(Self::I2C_REG_ADDR_MASK << Self::I2C_REG_ADDR_OFFSET);
+        self.0 |= ((i2c_reg_addr as u32) & Self::I2C_REG_ADDR_MASK) << Self::I2C_REG_ADDR_OFFSET;
+        self
+    }
+
+    /// ## Handle the I2C_REG_VAL field.
+    ///
+    /// Get and set the I2C device register value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    ///
+    /// let mut i2c = I2CControl(0x0000_0000);
+    /// assert_eq!(i2c.i2c_reg_val(), 0x00);
+    /// assert_eq!(i2c.set_i2c_reg_val(0xff).i2c_reg_val(), 0xff); // max value
+    /// ```
+    pub const fn i2c_reg_val(&self) -> u8 {
+        ((self.0 >> Self::I2C_REG_VAL_OFFSET) & Self::I2C_REG_VAL_MASK) as u8
+    }
+    pub fn set_i2c_reg_val(&mut self, i2c_reg_val: u8) -> &mut Self {
+        self.0 &= !(Self::I2C_REG_VAL_MASK << Self::I2C_REG_VAL_OFFSET);
+        self.0 |= ((i2c_reg_val as u32) & Self::I2C_REG_VAL_MASK) << Self::I2C_REG_VAL_OFFSET;
+        self
+    }
+
+    /// ## Build the I2C read command of a temperature sensor register.
+    ///
+    /// Start an I2C read of the `i2c_reg_addr` register of the sensor at `i2c_addr`.
+    /// The I2CControl register of the chip then has to be read back, and its response decoded
+    /// with `temperature()`.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// // local temperature (register 0x00) of a sensor at 0x4c
+    /// assert_eq!(
+    ///     I2CControl::read_temperature_cmd(0x4c, 0x00, Destination::Chip(0)),
+    ///     Command::write_reg(0x1c, 0x0199_0000, Destination::Chip(0))
+    /// );
+    /// ```
+    pub fn read_temperature_cmd(i2c_addr: u8, i2c_reg_addr: u8, dest: Destination) -> [u8; 11] {
+        let i2c = *Self(0)
+            .set_do_cmd()
+            .set_i2c_addr(i2c_addr)
+            .set_read()
+            .set_i2c_reg_addr(i2c_reg_addr);
+        Command::write_reg(Self::ADDR, i2c.val(), dest)
+    }
+
+    /// ## Decode a temperature from an I2CControl register response.
+    ///
+    /// The I2C register value is decoded as a signed integer in Celsius, which is the standard
+    /// range format of the usual hashboard sensors (TMP451, ADT7461, ...).
+    /// Returns `None` if the response is not an I2CControl register, or if the I2C
+    /// transaction is still busy.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::register::I2CControl;
+    /// use bm13xx_protocol::response::RegisterResponse;
+    ///
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x1c, reg_value: 0x0099_002d };
+    /// assert_eq!(I2CControl::temperature(&resp), Some(45.0));
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x1c, reg_value: 0x0099_00f6 };
+    /// assert_eq!(I2CControl::temperature(&resp), Some(-10.0));
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x1c, reg_value: 0x8199_0000 };
+    /// assert_eq!(I2CControl::temperature(&resp), None); // busy
+    /// let resp = RegisterResponse { chip_addr: 0, reg_addr: 0x00, reg_value: 0x0099_002d };
+    /// assert_eq!(I2CControl::temperature(&resp), None);
+    /// ```
+    pub fn temperature(resp: &RegisterResponse) -> Option<f32> {
+        let i2c = Self(resp.reg_value);
+        (resp.reg_addr == Self::ADDR && !i2c.is_busy()).then_some(i2c.i2c_reg_val() as i8 as f32)
+    }
 }
 
 impl core::fmt::Display for I2CControl {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("I2CControl").finish()
+        f.debug_struct("I2CControl")
+            .field("busy", &self.is_busy())
+            .field("do_cmd", &self.is_do_cmd())
+            .field("i2c_addr", &self.i2c_addr())
+            .field("read", &self.is_read())
+            .field("i2c_reg_addr", &self.i2c_reg_addr())
+            .field("i2c_reg_val", &self.i2c_reg_val())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for I2CControl {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "I2CControl {{  }}",);
+        defmt::write!(
+            fmt,
+            "I2CControl {{ busy: {}, do_cmd: {}, i2c_addr: {}, read: {}, i2c_reg_addr: {}, i2c_reg_val: {} }}",
+            self.is_busy(),
+            self.is_do_cmd(),
+            self.i2c_addr(),
+            self.is_read(),
+            self.i2c_reg_addr(),
+            self.i2c_reg_val(),
+        );
     }
 }
@@ -167,6 +167,27 @@ impl defmt::Format for MiscControl {
 /// # Misc Control register V2
 ///
 /// Used to control various settings.
+///
+/// The meaning of the B\[27:26\], B\[25:24\] and B\[19:16\] fields is not documented,
+/// they are cleared by the Core reset sequence.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{MiscControlV2, Register};
+///
+/// // value written by the BM1366/BM1370 Core reset sequence
+/// let mut misc = MiscControlV2(0x0000_C100); // BM1366 default value
+/// misc.set_core_return_nonce(0xf)
+///     .set_b27_26(0)
+///     .set_b25_24(0)
+///     .set_b19_16(0);
+/// assert_eq!(misc.val(), 0xF000_C100);
+/// assert_eq!(misc.core_return_nonce(), 0xf);
+/// assert_eq!(misc.b27_26(), 0);
+/// assert_eq!(misc.b25_24(), 0);
+/// assert_eq!(misc.b19_16(), 0);
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct MiscControlV2(pub u32);
 impl_boilerplate_for!(MiscControlV2);
@@ -280,13 +301,25 @@ impl MiscControlV2 {
 
 impl core::fmt::Display for MiscControlV2 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("MiscControlV2").finish()
+        f.debug_struct("MiscControlV2")
+            .field("core_return_nonce", &self.core_return_nonce())
+            .field("b27_26", &self.b27_26())
+            .field("b25_24", &self.b25_24())
+            .field("b19_16", &self.b19_16())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for MiscControlV2 {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "MiscControlV2 {{ }}",);
+        defmt::write!(
+            fmt,
+            "MiscControlV2 {{ core_return_nonce: {}, b27_26: {}, b25_24: {}, b19_16: {} }}",
+            self.core_return_nonce(),
+            self.b27_26(),
+            self.b25_24(),
+            self.b19_16(),
+        );
     }
 }
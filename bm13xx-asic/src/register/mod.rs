@@ -1,3 +1,5 @@
+use heapless::FnvIndexMap;
+
 pub trait Register {
     fn addr(&self) -> u8;
     fn val(&self) -> u32;
@@ -28,6 +30,40 @@ macro_rules! impl_boilerplate_for {
     };
 }
 
+/// ## Get the registers sorted by address
+///
+/// Deterministic view of a chip `registers` map, whose iteration order is unspecified.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::register::*;
+/// use heapless::FnvIndexMap;
+///
+/// let mut registers = FnvIndexMap::<u8, u32, 4>::new();
+/// registers.insert(TicketMask::ADDR, 0x0000_00ff).unwrap();
+/// registers.insert(ChipIdentification::ADDR, 0x1370_0000).unwrap();
+/// registers.insert(PLL0Parameter::ADDR, 0xc0a0_0241).unwrap();
+/// let regs = sorted_registers(&registers);
+/// assert_eq!(
+///     regs.as_slice(),
+///     &[
+///         (ChipIdentification::ADDR, 0x1370_0000),
+///         (PLL0Parameter::ADDR, 0xc0a0_0241),
+///         (TicketMask::ADDR, 0x0000_00ff),
+///     ]
+/// );
+/// ```
+pub fn sorted_registers<const N: usize>(
+    registers: &FnvIndexMap<u8, u32, N>,
+) -> heapless::Vec<(u8, u32), N> {
+    let mut regs: heapless::Vec<_, N> = registers
+        .iter()
+        .map(|(&reg_addr, &reg_val)| (reg_addr, reg_val))
+        .collect();
+    regs.sort_unstable_by_key(|&(reg_addr, _)| reg_addr);
+    regs
+}
+
 mod analog_mux;
 mod chip_identification;
 mod chip_nonce_offset;
@@ -46,7 +82,14 @@ mod nonce_counter;
 mod nonce_returned_timeout;
 mod pll_divider;
 mod pll_parameter;
+// The `RegXX` registers are undocumented, they are only known from captured init sequences
+// and their fields are named after their bit positions.
+mod reg_24;
+mod reg_30;
 mod reg_a8;
+mod reg_addr;
+mod reg_bc;
+mod reg_c4;
 mod return_group_pattern_status;
 mod returned_single_pattern_status;
 mod ticket_mask;
@@ -55,7 +98,7 @@ mod uart_relay;
 mod unknown;
 mod version_rolling;
 
-pub use analog_mux::{AnalogMuxControl, AnalogMuxControlV2};
+pub use analog_mux::{AnalogMuxChannel, AnalogMuxControl, AnalogMuxControlV2};
 pub use chip_identification::ChipIdentification;
 pub use chip_nonce_offset::{ChipNonceOffset, ChipNonceOffsetV2};
 pub use clock_order::{
@@ -76,14 +119,19 @@ pub use nonce_counter::{NonceErrorCounter, NonceOverflowCounter};
 pub use nonce_returned_timeout::NonceReturnedTimeout;
 pub use pll_divider::{PLL0Divider, PLL1Divider, PLL2Divider, PLL3Divider};
 pub use pll_parameter::{PLL0Parameter, PLL1Parameter, PLL2Parameter, PLL3Parameter};
+pub use reg_24::Reg24;
+pub use reg_30::Reg30;
 pub use reg_a8::RegA8;
+pub use reg_addr::RegAddr;
+pub use reg_bc::RegBC;
+pub use reg_c4::RegC4;
 pub use return_group_pattern_status::ReturnedGroupPatternStatus;
 pub use returned_single_pattern_status::ReturnedSinglePatternStatus;
 pub use ticket_mask::{TicketMask, TicketMask2};
 pub use timeout::TimeOut;
 pub use uart_relay::UARTRelay;
 pub use unknown::{
-    Reg24, Reg30, Reg34, RegAC, RegB0, RegB4, RegB8, RegBC, RegC0, RegC4, RegC8, RegCC, RegD0,
-    RegD4, RegD8, RegDC, RegE0, RegE4, RegE8, RegEC, RegF0, RegF4, RegF8, RegFC,
+    Reg34, RegAC, RegB0, RegB4, RegB8, RegC0, RegC8, RegCC, RegD0, RegD4, RegD8, RegDC, RegE0,
+    RegE4, RegE8, RegEC, RegF0, RegF4, RegF8, RegFC,
 };
 pub use version_rolling::VersionRolling;
@@ -27,6 +27,10 @@ impl defmt::Format for NonceErrorCounter {
 }
 
 /// # Nonce Overflow Counter register
+///
+/// Purely a read-only counter, there is no known policy to configure the chip reaction to a
+/// Nonce overflow. Read it with `RegAddr::NonceOverflowCounter.read_reg()`, it is never reset
+/// by writing it, use `overflows_since()` between two readings instead.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct NonceOverflowCounter(pub u32);
 impl_boilerplate_for!(NonceOverflowCounter);
@@ -34,20 +38,57 @@ impl_boilerplate_for!(NonceOverflowCounter);
 impl NonceOverflowCounter {
     pub const ADDR: u8 = 0x50;
 
-    // const OVRF_CNT_OFFSET: u8 = 0;
+    const OVRF_CNT_OFFSET: u8 = 0;
 
-    // const OVRF_CNT_MASK: u32 = 0xffff_ffff;
+    const OVRF_CNT_MASK: u32 = 0xffff_ffff;
+
+    /// ## Handle the OVRF_CNT field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::NonceOverflowCounter;
+    ///
+    /// assert_eq!(NonceOverflowCounter(0x0000_0000).ovrf_cnt(), 0); // BM1366 default value
+    /// assert_eq!(NonceOverflowCounter(0x0000_002a).ovrf_cnt(), 42);
+    /// ```
+    pub const fn ovrf_cnt(&self) -> u32 {
+        (self.0 >> Self::OVRF_CNT_OFFSET) & Self::OVRF_CNT_MASK
+    }
+
+    /// ## Get the number of overflows since a previous reading.
+    ///
+    /// The counter wraps around.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::NonceOverflowCounter;
+    ///
+    /// let previous = NonceOverflowCounter(40);
+    /// assert_eq!(NonceOverflowCounter(42).overflows_since(previous), 2);
+    /// assert_eq!(NonceOverflowCounter(1).overflows_since(NonceOverflowCounter(0xffff_ffff)), 2);
+    /// ```
+    pub const fn overflows_since(&self, previous: NonceOverflowCounter) -> u32 {
+        self.ovrf_cnt().wrapping_sub(previous.ovrf_cnt())
+    }
 }
 
 impl core::fmt::Display for NonceOverflowCounter {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("NonceOverflowCounter").finish()
+        f.debug_struct("NonceOverflowCounter")
+            .field("ovrf_cnt", &self.ovrf_cnt())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for NonceOverflowCounter {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "NonceOverflowCounter {{  }}",);
+        defmt::write!(
+            fmt,
+            "NonceOverflowCounter {{ ovrf_cnt: {} }}",
+            self.ovrf_cnt()
+        );
     }
 }
@@ -0,0 +1,67 @@
+use crate::register::Register;
+
+/// # Reg24 register
+///
+/// Reset to 0x0010_0000 (only B20 set) on both the BM1366 and the BM1370, never written by the drivers.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{Reg24, Register};
+///
+/// let reg_24 = Reg24(0x0010_0000); // BM1366 and BM1370 default value
+/// assert!(reg_24.is_b20());
+/// assert_eq!(Reg24(0).set_b20().val(), 0x0010_0000);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Reg24(pub u32);
+impl_boilerplate_for!(Reg24);
+
+impl Reg24 {
+    pub const ADDR: u8 = 0x24;
+
+    const B20_OFFSET: u8 = 20;
+
+    const B20_MASK: u32 = 0x1;
+
+    /// ## Handle the B20 field.
+    ///
+    /// Get and set the B20 state.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::Reg24;
+    ///
+    /// let mut reg_24 = Reg24(0x0010_0000); // BM1366 default value
+    /// assert!(reg_24.is_b20());
+    /// assert!(!reg_24.clr_b20().is_b20());
+    /// assert!(reg_24.set_b20().is_b20());
+    /// ```
+    pub const fn is_b20(&self) -> bool {
+        (self.0 >> Self::B20_OFFSET) & Self::B20_MASK == Self::B20_MASK
+    }
+    pub fn set_b20(&mut self) -> &mut Self {
+        self.0 |= Self::B20_MASK << Self::B20_OFFSET;
+        self
+    }
+    pub fn clr_b20(&mut self) -> &mut Self {
+        self.0 &= !(Self::B20_MASK << Self::B20_OFFSET);
+        self
+    }
+}
+
+impl core::fmt::Display for Reg24 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Reg24")
+            .field("b20", &self.is_b20())
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt-03")]
+impl defmt::Format for Reg24 {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "Reg24 {{ b20: {} }}", self.is_b20(),);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::register::Register;
+
+/// # Reg30 register
+///
+/// Reset to 0x70 on the BM1366 and 0x80 on the BM1370, likely a chip specific tuning value
+/// held in B\[7:0\]. Never written by the drivers.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{Reg30, Register};
+///
+/// assert_eq!(Reg30(0x0000_0070).b7_0(), 0x70); // BM1366 default value
+/// assert_eq!(Reg30(0x0000_0080).b7_0(), 0x80); // BM1370 default value
+/// assert_eq!(Reg30(0).set_b7_0(0x80).val(), 0x0000_0080);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Reg30(pub u32);
+impl_boilerplate_for!(Reg30);
+
+impl Reg30 {
+    pub const ADDR: u8 = 0x30;
+
+    const B7_0_OFFSET: u8 = 0;
+
+    const B7_0_MASK: u32 = 0xff;
+
+    /// ## Handle the B\[7:0\] field.
+    ///
+    /// Get and set the B\[7:0\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::Reg30;
+    ///
+    /// let mut reg_30 = Reg30(0x0000_0080); // BM1370 default value
+    /// assert_eq!(reg_30.b7_0(), 0x80);
+    /// assert_eq!(reg_30.set_b7_0(0x70).b7_0(), 0x70); // BM1366 default value
+    /// assert_eq!(reg_30.set_b7_0(0xff).b7_0(), 0xff); // max value
+    /// ```
+    pub const fn b7_0(&self) -> u8 {
+        ((self.0 >> Self::B7_0_OFFSET) & Self::B7_0_MASK) as u8
+    }
+    pub fn set_b7_0(&mut self, b7_0: u8) -> &mut Self {
+        self.0 &= !(Self::B7_0_MASK << Self::B7_0_OFFSET);
+        self.0 |= ((b7_0 as u32) & Self::B7_0_MASK) << Self::B7_0_OFFSET;
+        self
+    }
+}
+
+impl core::fmt::Display for Reg30 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Reg30").field("b7_0", &self.b7_0()).finish()
+    }
+}
+
+#[cfg(feature = "defmt-03")]
+impl defmt::Format for Reg30 {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "Reg30 {{ b7_0: {} }}", self.b7_0(),);
+    }
+}
@@ -1,6 +1,11 @@
 use crate::register::Register;
 
 /// # RegA8 register
+///
+/// Written at the beginning of the Core reset sequence:
+/// B8 and B\[7:4\] appear to gate the Core soft reset, that's why
+/// they are also accessible through the `core_reset` aliases.
+/// B\[18:16\] is set in the reset value and never touched by the drivers.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct RegA8(pub u32);
 impl_boilerplate_for!(RegA8);
@@ -8,16 +13,42 @@ impl_boilerplate_for!(RegA8);
 impl RegA8 {
     pub const ADDR: u8 = 0xA8;
 
+    const B18_16_OFFSET: u8 = 16;
     const B10_OFFSET: u8 = 10;
     const B8_OFFSET: u8 = 8;
     const B7_4_OFFSET: u8 = 4;
     const B3_0_OFFSET: u8 = 0;
 
+    const B18_16_MASK: u32 = 0x7;
     const B10_MASK: u32 = 0x1;
     const B8_MASK: u32 = 0x1;
     const B7_4_MASK: u32 = 0xf;
     const B3_0_MASK: u32 = 0xf;
 
+    /// ## Handle the B\[18:16\] field.
+    ///
+    /// Get and set the B\[18:16\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{RegA8, Register};
+    ///
+    /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
+    /// assert_eq!(reg_a8.b18_16(), 0x7);
+    /// assert_eq!(reg_a8.set_b18_16(0x0).b18_16(), 0x0);
+    /// assert_eq!(reg_a8.set_b18_16(0x8).b18_16(), 0x0); // out of bound value
+    /// assert_eq!(RegA8(0).set_b18_16(0x7).val(), 0x0007_0000); // reset value
+    /// ```
+    pub const fn b18_16(&self) -> u8 {
+        ((self.0 >> Self::B18_16_OFFSET) & Self::B18_16_MASK) as u8
+    }
+    pub fn set_b18_16(&mut self, b18_16: u8) -> &mut Self {
+        self.0 &= !(Self::B18_16_MASK << Self::B18_16_OFFSET);
+        self.0 |= ((b18_16 as u32) & Self::B18_16_MASK) << Self::B18_16_OFFSET;
+        self
+    }
+
     /// ## Handle the B10 field.
     ///
     /// Get and set the B10 state.
@@ -115,17 +146,85 @@ impl RegA8 {
         self.0 |= ((b3_0 as u32) & Self::B3_0_MASK) << Self::B3_0_OFFSET;
         self
     }
+
+    /// ## Handle the Core Reset field.
+    ///
+    /// Alias of the B8 field, set before soft resetting the Cores.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegA8;
+    ///
+    /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
+    /// assert!(!reg_a8.core_reset_enabled());
+    /// assert!(reg_a8.enable_core_reset().core_reset_enabled());
+    /// assert!(reg_a8.is_b8());
+    /// assert!(!reg_a8.disable_core_reset().core_reset_enabled());
+    /// ```
+    pub const fn core_reset_enabled(&self) -> bool {
+        self.is_b8()
+    }
+    pub fn enable_core_reset(&mut self) -> &mut Self {
+        self.set_b8()
+    }
+    pub fn disable_core_reset(&mut self) -> &mut Self {
+        self.clr_b8()
+    }
+
+    /// ## Handle the Core Reset Mask field.
+    ///
+    /// Alias of the B\[7:4\] field, all bits are set together with B8 before soft resetting the Cores.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{RegA8, Register};
+    ///
+    /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
+    /// assert_eq!(reg_a8.core_reset_mask(), 0);
+    /// assert_eq!(reg_a8.set_core_reset_mask(0xf).b7_4(), 0xf);
+    /// // same bytes as the `set_b8().set_b7_4(0xf)` sequence
+    /// assert_eq!(
+    ///     RegA8(0x0007_0000).enable_core_reset().set_core_reset_mask(0xf).val(),
+    ///     RegA8(0x0007_0000).set_b8().set_b7_4(0xf).val()
+    /// );
+    /// assert_eq!(
+    ///     RegA8(0x0007_0000).enable_core_reset().set_core_reset_mask(0xf).val(),
+    ///     0x0007_01f0
+    /// );
+    /// ```
+    pub const fn core_reset_mask(&self) -> u8 {
+        self.b7_4()
+    }
+    pub fn set_core_reset_mask(&mut self, mask: u8) -> &mut Self {
+        self.set_b7_4(mask)
+    }
 }
 
 impl core::fmt::Display for RegA8 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("RegA8").finish()
+        f.debug_struct("RegA8")
+            .field("b18_16", &self.b18_16())
+            .field("b10", &self.is_b10())
+            .field("b8", &self.is_b8())
+            .field("b7_4", &self.b7_4())
+            .field("b3_0", &self.b3_0())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for RegA8 {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "RegA8 {{  }}",);
+        defmt::write!(
+            fmt,
+            "RegA8 {{ b18_16: {}, b10: {}, b8: {}, b7_4: {}, b3_0: {} }}",
+            self.b18_16(),
+            self.is_b10(),
+            self.is_b8(),
+            self.b7_4(),
+            self.b3_0(),
+        );
     }
 }
@@ -0,0 +1,236 @@
+use crate::register::*;
+use crate::{CmdDelay, Error};
+use bm13xx_protocol::{
+    command::{Command, Destination},
+    response::RegisterResponse,
+};
+
+macro_rules! reg_addr {
+    ($($REG:ident),* $(,)?) => {
+        /// # Register Address
+        ///
+        /// Address of all the defined registers, to be used instead of raw `u8` addresses.
+        ///
+        /// Registers sharing the same address between chip generations (like `MiscControl` and
+        /// `MiscControlV2`) are listed once, using the first generation name.
+        ///
+        /// ### Example
+        ///
+        /// ```
+        /// use bm13xx_asic::{register::*, Error};
+        ///
+        /// assert_eq!(RegAddr::TicketMask as u8, TicketMask::ADDR);
+        /// assert_eq!(u8::from(RegAddr::MiscControl), MiscControlV2::ADDR);
+        /// assert_eq!(RegAddr::try_from(0xA8), Ok(RegAddr::RegA8));
+        /// assert_eq!(RegAddr::try_from(0x33), Err(Error::UnknownRegister { reg_addr: 0x33 }));
+        /// ```
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+        #[repr(u8)]
+        pub enum RegAddr {
+            $($REG = $REG::ADDR,)*
+        }
+
+        impl TryFrom<u8> for RegAddr {
+            type Error = Error;
+
+            fn try_from(reg_addr: u8) -> Result<Self, Self::Error> {
+                match reg_addr {
+                    $(addr if addr == $REG::ADDR => Ok(RegAddr::$REG),)*
+                    _ => Err(Error::UnknownRegister { reg_addr }),
+                }
+            }
+        }
+    };
+}
+
+reg_addr!(
+    ChipIdentification,
+    HashRate,
+    PLL0Parameter,
+    ChipNonceOffset,
+    HashCountingNumber,
+    TicketMask,
+    MiscControl,
+    I2CControl,
+    OrderedClockEnable,
+    Reg24,
+    FastUARTConfiguration,
+    UARTRelay,
+    Reg30,
+    Reg34,
+    TicketMask2,
+    CoreRegisterControl,
+    CoreRegisterValue,
+    ExternalTemperatureSensorRead,
+    ErrorFlag,
+    NonceErrorCounter,
+    NonceOverflowCounter,
+    AnalogMuxControl,
+    IoDriverStrenghtConfiguration,
+    TimeOut,
+    PLL1Parameter,
+    PLL2Parameter,
+    PLL3Parameter,
+    OrderedClockMonitor,
+    PLL0Divider,
+    PLL1Divider,
+    PLL2Divider,
+    PLL3Divider,
+    ClockOrderControl0,
+    ClockOrderControl1,
+    ClockOrderStatus,
+    FrequencySweepControl1,
+    GoldenNonceForSweepReturn,
+    ReturnedGroupPatternStatus,
+    NonceReturnedTimeout,
+    ReturnedSinglePatternStatus,
+    VersionRolling,
+    RegA8,
+    RegAC,
+    RegB0,
+    RegB4,
+    RegB8,
+    RegBC,
+    RegC0,
+    RegC4,
+    RegC8,
+    RegCC,
+    RegD0,
+    RegD4,
+    RegD8,
+    RegDC,
+    RegE0,
+    RegE4,
+    RegE8,
+    RegEC,
+    RegF0,
+    RegF4,
+    RegF8,
+    RegFC,
+);
+
+impl From<RegAddr> for u8 {
+    fn from(reg_addr: RegAddr) -> u8 {
+        reg_addr as u8
+    }
+}
+
+impl RegAddr {
+    /// ## Check if the register is read-only
+    ///
+    /// Status, measurement and counter registers are only read by the drivers, writing them is
+    /// at best useless and can disturb the chip state.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{ErrorFlag, RegAddr, TicketMask};
+    ///
+    /// assert!(RegAddr::ErrorFlag.is_read_only());
+    /// assert!(!RegAddr::TicketMask.is_read_only());
+    /// assert_eq!(RegAddr::is_read_only_addr(ErrorFlag::ADDR), Some(true));
+    /// assert_eq!(RegAddr::is_read_only_addr(TicketMask::ADDR), Some(false));
+    /// assert_eq!(RegAddr::is_read_only_addr(0x33), None); // unknown register
+    /// ```
+    pub const fn is_read_only(self) -> bool {
+        matches!(
+            self,
+            RegAddr::ChipIdentification
+                | RegAddr::HashRate
+                | RegAddr::ExternalTemperatureSensorRead
+                | RegAddr::ErrorFlag
+                | RegAddr::NonceErrorCounter
+                | RegAddr::NonceOverflowCounter
+                | RegAddr::ClockOrderStatus
+                | RegAddr::GoldenNonceForSweepReturn
+                | RegAddr::ReturnedGroupPatternStatus
+                | RegAddr::ReturnedSinglePatternStatus
+        )
+    }
+
+    /// ## Check if the register at a raw address is read-only
+    ///
+    /// See `is_read_only()`, returns `None` for an unknown register.
+    pub fn is_read_only_addr(reg_addr: u8) -> Option<bool> {
+        RegAddr::try_from(reg_addr).ok().map(RegAddr::is_read_only)
+    }
+
+    /// ## Build a Read Register command for this register
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegAddr;
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// assert_eq!(
+    ///     RegAddr::ChipIdentification.read_reg(Destination::All),
+    ///     Command::read_reg(0x00, Destination::All)
+    /// );
+    /// ```
+    pub fn read_reg(self, dest: Destination) -> [u8; 7] {
+        Command::read_reg(self.into(), dest)
+    }
+
+    /// ## Build a Write Register command for this register
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegAddr;
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// assert_eq!(
+    ///     RegAddr::MiscControl.write_reg(0x0000_7A31, Destination::Chip(64)),
+    ///     [0x55, 0xAA, 0x41, 0x09, 0x40, 0x18, 0x00, 0x00, 0x7A, 0x31, 0x11]
+    /// );
+    /// ```
+    pub fn write_reg(self, reg_val: u32, dest: Destination) -> [u8; 11] {
+        Command::write_reg(self.into(), reg_val, dest)
+    }
+
+    /// ## Build a Write Register command and its Read Register check
+    ///
+    /// The read back command targets the same `dest`, its responses can be checked
+    /// with `is_written()`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::{register::RegAddr, CmdDelay};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    /// use bm13xx_protocol::response::RegisterResponse;
+    ///
+    /// let (write, read) = RegAddr::TicketMask.write_and_verify(0x0000_00ff, Destination::All);
+    /// assert_eq!(write, CmdDelay { cmd: Command::write_reg(0x14, 0x0000_00ff, Destination::All), delay_ms: 0 });
+    /// assert_eq!(read, Command::read_reg(0x14, Destination::All));
+    /// let resp = RegisterResponse { chip_addr: 2, reg_addr: 0x14, reg_value: 0x0000_00ff };
+    /// assert!(RegAddr::TicketMask.is_written(0x0000_00ff, Destination::All, &resp));
+    /// assert!(RegAddr::TicketMask.is_written(0x0000_00ff, Destination::Chip(2), &resp));
+    /// assert!(!RegAddr::TicketMask.is_written(0x0000_00ff, Destination::Chip(4), &resp)); // other chip
+    /// assert!(!RegAddr::TicketMask.is_written(0x0000_003f, Destination::All, &resp)); // other value
+    /// assert!(!RegAddr::MiscControl.is_written(0x0000_00ff, Destination::All, &resp)); // other register
+    /// ```
+    pub fn write_and_verify(self, reg_val: u32, dest: Destination) -> (CmdDelay, [u8; 7]) {
+        (
+            CmdDelay {
+                cmd: self.write_reg(reg_val, dest),
+                delay_ms: 0,
+            },
+            self.read_reg(dest),
+        )
+    }
+
+    /// ## Check a Read Register response against a written value
+    ///
+    /// See `write_and_verify()`.
+    pub fn is_written(self, reg_val: u32, dest: Destination, resp: &RegisterResponse) -> bool {
+        resp.reg_addr == self as u8
+            && resp.reg_value == reg_val
+            && match dest {
+                Destination::All => true,
+                Destination::Chip(chip_addr) => resp.chip_addr == chip_addr,
+            }
+    }
+}
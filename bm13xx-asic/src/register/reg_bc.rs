@@ -0,0 +1,155 @@
+use crate::register::Register;
+
+/// # RegBC register
+///
+/// Reset to 0x0000_3313 on both the BM1366 and the BM1370, 4 independent looking nibbles,
+/// never written by the drivers.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{RegBC, Register};
+///
+/// let reg_bc = RegBC(0x0000_3313); // BM1366/BM1370 default value
+/// assert_eq!(
+///     (reg_bc.b15_12(), reg_bc.b11_8(), reg_bc.b7_4(), reg_bc.b3_0()),
+///     (0x3, 0x3, 0x1, 0x3)
+/// );
+/// let mut reg_bc = RegBC(0);
+/// reg_bc.set_b15_12(0x3).set_b11_8(0x3).set_b7_4(0x1).set_b3_0(0x3);
+/// assert_eq!(reg_bc.val(), 0x0000_3313);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RegBC(pub u32);
+impl_boilerplate_for!(RegBC);
+
+impl RegBC {
+    pub const ADDR: u8 = 0xBC;
+
+    const B15_12_OFFSET: u8 = 12;
+    const B11_8_OFFSET: u8 = 8;
+    const B7_4_OFFSET: u8 = 4;
+    const B3_0_OFFSET: u8 = 0;
+
+    const B15_12_MASK: u32 = 0xf;
+    const B11_8_MASK: u32 = 0xf;
+    const B7_4_MASK: u32 = 0xf;
+    const B3_0_MASK: u32 = 0xf;
+
+    /// ## Handle the B\[15:12\] field.
+    ///
+    /// Get and set the B\[15:12\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegBC;
+    ///
+    /// let mut reg_bc = RegBC(0x0000_3313); // BM1366/BM1370 default value
+    /// assert_eq!(reg_bc.b15_12(), 0x3);
+    /// assert_eq!(reg_bc.set_b15_12(0xf).b15_12(), 0xf); // max value
+    /// assert_eq!(reg_bc.set_b15_12(0x10).b15_12(), 0); // out of bound value
+    /// ```
+    pub const fn b15_12(&self) -> u8 {
+        ((self.0 >> Self::B15_12_OFFSET) & Self::B15_12_MASK) as u8
+    }
+    pub fn set_b15_12(&mut self, b15_12: u8) -> &mut Self {
+        self.0 &= !(Self::B15_12_MASK << Self::B15_12_OFFSET);
+        self.0 |= ((b15_12 as u32) & Self::B15_12_MASK) << Self::B15_12_OFFSET;
+        self
+    }
+
+    /// ## Handle the B\[11:8\] field.
+    ///
+    /// Get and set the B\[11:8\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegBC;
+    ///
+    /// let mut reg_bc = RegBC(0x0000_3313); // BM1366/BM1370 default value
+    /// assert_eq!(reg_bc.b11_8(), 0x3);
+    /// assert_eq!(reg_bc.set_b11_8(0xf).b11_8(), 0xf); // max value
+    /// assert_eq!(reg_bc.set_b11_8(0x10).b11_8(), 0); // out of bound value
+    /// ```
+    pub const fn b11_8(&self) -> u8 {
+        ((self.0 >> Self::B11_8_OFFSET) & Self::B11_8_MASK) as u8
+    }
+    pub fn set_b11_8(&mut self, b11_8: u8) -> &mut Self {
+        self.0 &= !(Self::B11_8_MASK << Self::B11_8_OFFSET);
+        self.0 |= ((b11_8 as u32) & Self::B11_8_MASK) << Self::B11_8_OFFSET;
+        self
+    }
+
+    /// ## Handle the B\[7:4\] field.
+    ///
+    /// Get and set the B\[7:4\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegBC;
+    ///
+    /// let mut reg_bc = RegBC(0x0000_3313); // BM1366/BM1370 default value
+    /// assert_eq!(reg_bc.b7_4(), 0x1);
+    /// assert_eq!(reg_bc.set_b7_4(0xf).b7_4(), 0xf); // max value
+    /// assert_eq!(reg_bc.set_b7_4(0x10).b7_4(), 0); // out of bound value
+    /// ```
+    pub const fn b7_4(&self) -> u8 {
+        ((self.0 >> Self::B7_4_OFFSET) & Self::B7_4_MASK) as u8
+    }
+    pub fn set_b7_4(&mut self, b7_4: u8) -> &mut Self {
+        self.0 &= !(Self::B7_4_MASK << Self::B7_4_OFFSET);
+        self.0 |= ((b7_4 as u32) & Self::B7_4_MASK) << Self::B7_4_OFFSET;
+        self
+    }
+
+    /// ## Handle the B\[3:0\] field.
+    ///
+    /// Get and set the B\[3:0\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegBC;
+    ///
+    /// let mut reg_bc = RegBC(0x0000_3313); // BM1366/BM1370 default value
+    /// assert_eq!(reg_bc.b3_0(), 0x3);
+    /// assert_eq!(reg_bc.set_b3_0(0xf).b3_0(), 0xf); // max value
+    /// assert_eq!(reg_bc.set_b3_0(0x10).b3_0(), 0); // out of bound value
+    /// ```
+    pub const fn b3_0(&self) -> u8 {
+        ((self.0 >> Self::B3_0_OFFSET) & Self::B3_0_MASK) as u8
+    }
+    pub fn set_b3_0(&mut self, b3_0: u8) -> &mut Self {
+        self.0 &= !(Self::B3_0_MASK << Self::B3_0_OFFSET);
+        self.0 |= ((b3_0 as u32) & Self::B3_0_MASK) << Self::B3_0_OFFSET;
+        self
+    }
+}
+
+impl core::fmt::Display for RegBC {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RegBC")
+            .field("b15_12", &self.b15_12())
+            .field("b11_8", &self.b11_8())
+            .field("b7_4", &self.b7_4())
+            .field("b3_0", &self.b3_0())
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt-03")]
+impl defmt::Format for RegBC {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "RegBC {{ b15_12: {}, b11_8: {}, b7_4: {}, b3_0: {} }}",
+            self.b15_12(),
+            self.b11_8(),
+            self.b7_4(),
+            self.b3_0(),
+        );
+    }
+}
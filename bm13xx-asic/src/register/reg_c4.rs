@@ -0,0 +1,160 @@
+use crate::register::Register;
+
+/// # RegC4 register
+///
+/// Reset to null on the BM1366 and to 0x0000_B850 (4 independent looking nibbles) on the BM1370,
+/// never written by the drivers.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{RegC4, Register};
+///
+/// let reg_c4 = RegC4(0x0000_B850); // BM1370 default value
+/// assert_eq!(
+///     (reg_c4.b15_12(), reg_c4.b11_8(), reg_c4.b7_4(), reg_c4.b3_0()),
+///     (0xb, 0x8, 0x5, 0x0)
+/// );
+/// let reg_c4 = RegC4(0x0000_0000); // BM1366 default value
+/// assert_eq!(
+///     (reg_c4.b15_12(), reg_c4.b11_8(), reg_c4.b7_4(), reg_c4.b3_0()),
+///     (0x0, 0x0, 0x0, 0x0)
+/// );
+/// let mut reg_c4 = RegC4(0);
+/// reg_c4.set_b15_12(0xb).set_b11_8(0x8).set_b7_4(0x5).set_b3_0(0x0);
+/// assert_eq!(reg_c4.val(), 0x0000_B850);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RegC4(pub u32);
+impl_boilerplate_for!(RegC4);
+
+impl RegC4 {
+    pub const ADDR: u8 = 0xC4;
+
+    const B15_12_OFFSET: u8 = 12;
+    const B11_8_OFFSET: u8 = 8;
+    const B7_4_OFFSET: u8 = 4;
+    const B3_0_OFFSET: u8 = 0;
+
+    const B15_12_MASK: u32 = 0xf;
+    const B11_8_MASK: u32 = 0xf;
+    const B7_4_MASK: u32 = 0xf;
+    const B3_0_MASK: u32 = 0xf;
+
+    /// ## Handle the B\[15:12\] field.
+    ///
+    /// Get and set the B\[15:12\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegC4;
+    ///
+    /// let mut reg_c4 = RegC4(0x0000_B850); // BM1370 default value
+    /// assert_eq!(reg_c4.b15_12(), 0xb);
+    /// assert_eq!(reg_c4.set_b15_12(0xf).b15_12(), 0xf); // max value
+    /// assert_eq!(reg_c4.set_b15_12(0x10).b15_12(), 0); // out of bound value
+    /// ```
+    pub const fn b15_12(&self) -> u8 {
+        ((self.0 >> Self::B15_12_OFFSET) & Self::B15_12_MASK) as u8
+    }
+    pub fn set_b15_12(&mut self, b15_12: u8) -> &mut Self {
+        self.0 &= !(Self::B15_12_MASK << Self::B15_12_OFFSET);
+        self.0 |= ((b15_12 as u32) & Self::B15_12_MASK) << Self::B15_12_OFFSET;
+        self
+    }
+
+    /// ## Handle the B\[11:8\] field.
+    ///
+    /// Get and set the B\[11:8\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegC4;
+    ///
+    /// let mut reg_c4 = RegC4(0x0000_B850); // BM1370 default value
+    /// assert_eq!(reg_c4.b11_8(), 0x8);
+    /// assert_eq!(reg_c4.set_b11_8(0xf).b11_8(), 0xf); // max value
+    /// assert_eq!(reg_c4.set_b11_8(0x10).b11_8(), 0); // out of bound value
+    /// ```
+    pub const fn b11_8(&self) -> u8 {
+        ((self.0 >> Self::B11_8_OFFSET) & Self::B11_8_MASK) as u8
+    }
+    pub fn set_b11_8(&mut self, b11_8: u8) -> &mut Self {
+        self.0 &= !(Self::B11_8_MASK << Self::B11_8_OFFSET);
+        self.0 |= ((b11_8 as u32) & Self::B11_8_MASK) << Self::B11_8_OFFSET;
+        self
+    }
+
+    /// ## Handle the B\[7:4\] field.
+    ///
+    /// Get and set the B\[7:4\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegC4;
+    ///
+    /// let mut reg_c4 = RegC4(0x0000_B850); // BM1370 default value
+    /// assert_eq!(reg_c4.b7_4(), 0x5);
+    /// assert_eq!(reg_c4.set_b7_4(0xf).b7_4(), 0xf); // max value
+    /// assert_eq!(reg_c4.set_b7_4(0x10).b7_4(), 0); // out of bound value
+    /// ```
+    pub const fn b7_4(&self) -> u8 {
+        ((self.0 >> Self::B7_4_OFFSET) & Self::B7_4_MASK) as u8
+    }
+    pub fn set_b7_4(&mut self, b7_4: u8) -> &mut Self {
+        self.0 &= !(Self::B7_4_MASK << Self::B7_4_OFFSET);
+        self.0 |= ((b7_4 as u32) & Self::B7_4_MASK) << Self::B7_4_OFFSET;
+        self
+    }
+
+    /// ## Handle the B\[3:0\] field.
+    ///
+    /// Get and set the B\[3:0\] value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::RegC4;
+    ///
+    /// let mut reg_c4 = RegC4(0x0000_B850); // BM1370 default value
+    /// assert_eq!(reg_c4.b3_0(), 0x0);
+    /// assert_eq!(reg_c4.set_b3_0(0xf).b3_0(), 0xf); // max value
+    /// assert_eq!(reg_c4.set_b3_0(0x10).b3_0(), 0); // out of bound value
+    /// ```
+    pub const fn b3_0(&self) -> u8 {
+        ((self.0 >> Self::B3_0_OFFSET) & Self::B3_0_MASK) as u8
+    }
+    pub fn set_b3_0(&mut self, b3_0: u8) -> &mut Self {
+        self.0 &= !(Self::B3_0_MASK << Self::B3_0_OFFSET);
+        self.0 |= ((b3_0 as u32) & Self::B3_0_MASK) << Self::B3_0_OFFSET;
+        self
+    }
+}
+
+impl core::fmt::Display for RegC4 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RegC4")
+            .field("b15_12", &self.b15_12())
+            .field("b11_8", &self.b11_8())
+            .field("b7_4", &self.b7_4())
+            .field("b3_0", &self.b3_0())
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt-03")]
+impl defmt::Format for RegC4 {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "RegC4 {{ b15_12: {}, b11_8: {}, b7_4: {}, b3_0: {} }}",
+            self.b15_12(),
+            self.b11_8(),
+            self.b7_4(),
+            self.b3_0(),
+        );
+    }
+}
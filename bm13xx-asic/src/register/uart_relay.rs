@@ -1,6 +1,22 @@
 use crate::register::Register;
 
 /// # UART Relay register
+///
+/// Used to relay the UART signals through the voltage domains.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{Register, UARTRelay};
+///
+/// let mut uart_relay = UARTRelay(0x000f_0000); // BM1366 default value
+/// uart_relay.set_gap_cnt(0x2f).enable_ro_relay().enable_co_relay();
+/// assert_eq!(uart_relay.val(), 0x002f_0003);
+/// let uart_relay = UARTRelay(uart_relay.val());
+/// assert_eq!(uart_relay.gap_cnt(), 0x2f);
+/// assert!(uart_relay.ro_relay_enabled());
+/// assert!(uart_relay.co_relay_enabled());
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct UARTRelay(pub u32);
 impl_boilerplate_for!(UARTRelay);
@@ -88,13 +104,23 @@ impl UARTRelay {
 
 impl core::fmt::Display for UARTRelay {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("UARTRelay").finish()
+        f.debug_struct("UARTRelay")
+            .field("gap_cnt", &self.gap_cnt())
+            .field("ro_relay_enabled", &self.ro_relay_enabled())
+            .field("co_relay_enabled", &self.co_relay_enabled())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for UARTRelay {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "UARTRelay {{  }}",);
+        defmt::write!(
+            fmt,
+            "UARTRelay {{ gap_cnt: {}, ro_relay_enabled: {}, co_relay_enabled: {} }}",
+            self.gap_cnt(),
+            self.ro_relay_enabled(),
+            self.co_relay_enabled(),
+        );
     }
 }
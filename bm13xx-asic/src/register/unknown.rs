@@ -26,16 +26,12 @@ macro_rules! unknown {
     };
 }
 
-unknown!(Reg24, 0x24);
-unknown!(Reg30, 0x30);
 unknown!(Reg34, 0x34);
 unknown!(RegAC, 0xAC);
 unknown!(RegB0, 0xB0);
 unknown!(RegB4, 0xB4);
 unknown!(RegB8, 0xB8);
-unknown!(RegBC, 0xBC);
 unknown!(RegC0, 0xC0);
-unknown!(RegC4, 0xC4);
 unknown!(RegC8, 0xC8);
 unknown!(RegCC, 0xCC);
 unknown!(RegD0, 0xD0);
@@ -1,6 +1,21 @@
 use crate::register::Register;
 
 /// # Version Rolling register
+///
+/// Used to enable the version rolling and set the rolled bits of the block version.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{Register, VersionRolling};
+///
+/// // value written by the Version Rolling sequence
+/// let mut vers_roll = VersionRolling(0x0000_FFFF); // default value
+/// vers_roll.enable().set_mask(0x1fff_e000);
+/// assert_eq!(vers_roll.val(), 0x9000_FFFF);
+/// assert!(vers_roll.enabled());
+/// assert_eq!(vers_roll.mask(), 0x1fff_e000);
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct VersionRolling(pub u32);
 impl_boilerplate_for!(VersionRolling);
@@ -69,13 +84,21 @@ impl VersionRolling {
 
 impl core::fmt::Display for VersionRolling {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("VersionRolling").finish()
+        f.debug_struct("VersionRolling")
+            .field("enabled", &self.enabled())
+            .field("mask", &self.mask())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for VersionRolling {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "VersionRolling {{  }}",);
+        defmt::write!(
+            fmt,
+            "VersionRolling {{ enabled: {}, mask: {} }}",
+            self.enabled(),
+            self.mask(),
+        );
     }
 }
@@ -0,0 +1,310 @@
+//! Helpers to write tests against command sequences.
+
+use crate::{
+    chip_address,
+    nonce::{NonceField, NonceLayout},
+    register::{
+        ChipNonceOffset, FastUARTConfiguration, PLL0Parameter, RegA8, TicketMask, VersionRolling,
+    },
+    Asic, CmdDelay, SequenceStep,
+};
+use bm13xx_protocol::command::{Command, Destination};
+use core::fmt::Write;
+use fugit::HertzU64;
+use heapless::{String, Vec};
+
+/// ## Get the valid bytes of each command of a sequence
+///
+/// See `CmdDelay::bytes()`.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{test_util::frames, CmdDelay};
+/// use bm13xx_protocol::command::{Command, Destination};
+///
+/// let seq = [CmdDelay { cmd: Command::write_reg(0x14, 0xff, Destination::All), delay_ms: 10 }];
+/// assert_eq!(frames::<4>(&seq), [[0x55, 0xAA, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08]]);
+/// ```
+pub fn frames<const N: usize>(seq: &[CmdDelay]) -> Vec<&[u8], N> {
+    seq.iter().take(N).map(CmdDelay::bytes).collect()
+}
+
+/// ## Render a sequence as a multi-line hex string
+///
+/// One line per command: the frame bytes in uppercase hex, followed by the delay in ms.
+/// The output is truncated if it does not fit in `N` bytes.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{test_util::hex_dump, CmdDelay};
+/// use bm13xx_protocol::command::{Command, Destination};
+///
+/// let seq = [
+///     CmdDelay { cmd: Command::write_reg(0x14, 0xff, Destination::All), delay_ms: 10 },
+///     CmdDelay { cmd: Command::write_reg(0x54, 0x03, Destination::Chip(2)), delay_ms: 0 },
+/// ];
+/// assert_eq!(
+///     hex_dump::<256>(&seq),
+///     "55 AA 51 09 00 14 00 00 00 FF 08 +10ms\n\
+///      55 AA 41 09 02 54 00 00 00 03 0A +0ms\n"
+/// );
+/// ```
+pub fn hex_dump<const N: usize>(seq: &[CmdDelay]) -> String<N> {
+    let mut dump = String::new();
+    for step in seq {
+        for b in step.bytes() {
+            if write!(dump, "{b:02X} ").is_err() {
+                return dump;
+            }
+        }
+        if writeln!(dump, "+{}ms", step.delay_ms).is_err() {
+            return dump;
+        }
+    }
+    dump
+}
+
+/// ## Compare a sequence against golden frames
+///
+/// Return the index of the first frame which differs from the `golden` one, or of the first
+/// missing or extra frame if the lengths differ.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{test_util::frames_equal, CmdDelay};
+/// use bm13xx_protocol::command::{Command, Destination};
+///
+/// let seq = [
+///     CmdDelay { cmd: Command::write_reg(0x14, 0xff, Destination::All), delay_ms: 10 },
+///     CmdDelay { cmd: Command::write_reg(0x54, 0x03, Destination::Chip(2)), delay_ms: 0 },
+/// ];
+/// let golden: [&[u8]; 2] = [
+///     &[0x55, 0xAA, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08],
+///     &[0x55, 0xAA, 0x41, 0x09, 0x02, 0x54, 0x00, 0x00, 0x00, 0x03, 0x0A],
+/// ];
+/// assert_eq!(frames_equal(&seq, &golden), Ok(()));
+/// let golden: [&[u8]; 2] = [
+///     &[0x55, 0xAA, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08],
+///     &[0x55, 0xAA, 0x41, 0x09, 0x02, 0x54, 0x00, 0x00, 0x00, 0x04, 0x0A], // single byte difference
+/// ];
+/// assert_eq!(frames_equal(&seq, &golden), Err(1));
+/// assert_eq!(frames_equal(&seq[..1], &golden), Err(1)); // missing frame
+/// assert_eq!(frames_equal(&seq, &golden[..0]), Err(0)); // extra frame
+/// ```
+pub fn frames_equal(seq: &[CmdDelay], golden: &[&[u8]]) -> Result<(), usize> {
+    if let Some(i) = seq
+        .iter()
+        .zip(golden)
+        .position(|(step, frame)| step.bytes() != *frame)
+    {
+        return Err(i);
+    }
+    if seq.len() != golden.len() {
+        return Err(seq.len().min(golden.len()));
+    }
+    Ok(())
+}
+
+/// # Dummy Asic
+///
+/// Chip independent `Asic` model, to exercise the `Asic` provided methods.
+///
+/// Each `*_next()` sequence is `seq_len` frames long (one frame per chip for
+/// `split_nonce_between_chips_next()`), with a 10 ms delay, and the model is updated frame by
+/// frame like a real chip:
+/// - `init_next()` writes the TicketMask matching the difficulty,
+/// - `reset_core_next()` writes RegA8 with the frame index,
+/// - `set_hash_freq_next()` ramps linearly to the target, writing PLL0Parameter with the
+///   frequency in MHz, and has no frame if the target is already reached,
+/// - `set_baudrate_next()` writes FastUARTConfiguration with the baudrate,
+/// - `split_nonce_between_chips_next()` writes ChipNonceOffset with the chip index to each chip,
+/// - `set_version_rolling_next()` writes VersionRolling with the mask.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{test_util::DummyAsic, Asic};
+/// use bm13xx_protocol::command::{Command, Destination};
+/// use fugit::HertzU64;
+///
+/// let mut dummy = DummyAsic::default();
+/// assert_eq!(dummy.hash_freq(), HertzU64::MHz(50));
+/// let seq: Vec<_> = core::iter::from_fn(|| dummy.set_hash_freq_next(HertzU64::MHz(500))).collect();
+/// assert_eq!(seq.len(), 2);
+/// assert_eq!(seq[0].cmd, Command::write_reg(0x08, 275, Destination::All));
+/// assert_eq!(seq[1].cmd, Command::write_reg(0x08, 500, Destination::All));
+/// assert_eq!(dummy.hash_freq(), HertzU64::MHz(500));
+/// assert_eq!(dummy.set_hash_freq_next(HertzU64::MHz(500)), None); // already there
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DummyAsic {
+    pub core_count: usize,
+    pub core_small_core_count: usize,
+    pub small_core_count: usize,
+    pub hash_freq: HertzU64,
+    pub ticket_mask: u32,
+    pub version_mask: Option<u32>,
+    /// Number of frames of each sequence
+    pub seq_len: usize,
+    seq_step: SequenceStep,
+    ramp_from: HertzU64,
+}
+
+impl Default for DummyAsic {
+    /// 1 Core of 4 Small Cores at 50 MHz, 2 frames per sequence.
+    fn default() -> Self {
+        DummyAsic {
+            core_count: 1,
+            core_small_core_count: 4,
+            small_core_count: 4,
+            hash_freq: HertzU64::MHz(50),
+            ticket_mask: 0,
+            version_mask: None,
+            seq_len: 2,
+            seq_step: SequenceStep::None,
+            ramp_from: HertzU64::MHz(50),
+        }
+    }
+}
+
+impl DummyAsic {
+    /// 128 Cores of 16 Small Cores, 2040 of them enabled, like a BM1370, at 500 MHz.
+    pub fn bm1370_like() -> Self {
+        DummyAsic {
+            core_count: 128,
+            core_small_core_count: 16,
+            small_core_count: 2040,
+            hash_freq: HertzU64::MHz(500),
+            ..Default::default()
+        }
+    }
+
+    /// Get the 1-based index of the next frame of the sequence `step`, or end the sequence.
+    fn next_frame(&mut self, step: fn(usize) -> SequenceStep, len: usize) -> Option<usize> {
+        // a new sequence is started whatever the current step was
+        let i = (2..=len + 1)
+            .find(|&i| self.seq_step == step(i - 1))
+            .unwrap_or(1);
+        if i > len {
+            self.seq_step = SequenceStep::None;
+            return None;
+        }
+        self.seq_step = step(i);
+        Some(i)
+    }
+
+    fn frame(reg_addr: u8, value: u32, dest: Destination) -> CmdDelay {
+        CmdDelay {
+            cmd: Command::write_reg(reg_addr, value, dest),
+            delay_ms: 10,
+        }
+    }
+}
+
+impl Asic for DummyAsic {
+    fn reset(&mut self) {
+        *self = DummyAsic {
+            core_count: self.core_count,
+            core_small_core_count: self.core_small_core_count,
+            small_core_count: self.small_core_count,
+            seq_len: self.seq_len,
+            ..Default::default()
+        };
+    }
+    fn chip_id(&self) -> u16 {
+        0x1234
+    }
+    fn core_count(&self) -> usize {
+        self.core_count
+    }
+    fn core_small_core_count(&self) -> usize {
+        self.core_small_core_count
+    }
+    fn small_core_count(&self) -> usize {
+        self.small_core_count
+    }
+    fn cno_interval(&self) -> usize {
+        0
+    }
+    fn cno_bits(&self) -> u32 {
+        0
+    }
+    fn hash_freq(&self) -> HertzU64 {
+        self.hash_freq
+    }
+    fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
+        self.hash_freq = freq;
+        self
+    }
+    fn init_next(&mut self, difficulty: u32) -> Option<CmdDelay> {
+        self.next_frame(SequenceStep::Init, self.seq_len)?;
+        self.ticket_mask = TicketMask::from_difficulty(difficulty).0;
+        Some(Self::frame(
+            TicketMask::ADDR,
+            self.ticket_mask,
+            Destination::All,
+        ))
+    }
+    fn set_baudrate_next(
+        &mut self,
+        baudrate: u32,
+        _chain_domain_cnt: usize,
+        _domain_asic_cnt: usize,
+        _asic_addr_interval: usize,
+    ) -> Option<CmdDelay> {
+        self.next_frame(SequenceStep::Baudrate, self.seq_len)?;
+        Some(Self::frame(
+            FastUARTConfiguration::ADDR,
+            baudrate,
+            Destination::All,
+        ))
+    }
+    fn reset_core_next(&mut self, dest: Destination) -> Option<CmdDelay> {
+        let i = self.next_frame(SequenceStep::ResetCore, self.seq_len)?;
+        Some(Self::frame(RegA8::ADDR, i as u32, dest))
+    }
+    fn set_hash_freq_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
+        if self.seq_step == SequenceStep::None {
+            if self.hash_freq == target_freq {
+                return None;
+            }
+            self.ramp_from = self.hash_freq;
+        }
+        let i = self.next_frame(SequenceStep::HashFreq, self.seq_len)?;
+        let from = self.ramp_from.raw() as i128;
+        let delta = target_freq.raw() as i128 - from;
+        self.hash_freq =
+            HertzU64::from_raw((from + delta * i as i128 / self.seq_len as i128) as u64);
+        Some(Self::frame(
+            PLL0Parameter::ADDR,
+            self.hash_freq.to_MHz() as u32,
+            Destination::All,
+        ))
+    }
+    fn split_nonce_between_chips_next(
+        &mut self,
+        chain_asic_num: usize,
+        asic_addr_interval: usize,
+    ) -> Option<CmdDelay> {
+        let i = self.next_frame(SequenceStep::SplitNonce, chain_asic_num)?;
+        let chip_addr = chip_address(i - 1, asic_addr_interval)?;
+        Some(Self::frame(
+            ChipNonceOffset::ADDR,
+            i as u32 - 1,
+            Destination::Chip(chip_addr),
+        ))
+    }
+    fn set_version_rolling_next(&mut self, mask: u32) -> Option<CmdDelay> {
+        self.next_frame(SequenceStep::VersionRolling, self.seq_len)?;
+        self.version_mask = Some(mask);
+        Some(Self::frame(VersionRolling::ADDR, mask, Destination::All))
+    }
+    fn nonce_layout(&self) -> NonceLayout {
+        let core_bits = self.core_count.next_power_of_two().ilog2();
+        NonceLayout {
+            core_id: NonceField::new(u32::BITS - core_bits, core_bits),
+            small_core_id: None,
+            chip_addr: None,
+            chip_addr_rolling: None,
+        }
+    }
+}
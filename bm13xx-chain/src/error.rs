@@ -18,6 +18,9 @@ pub enum Error<IO, B, R> {
     /// The BM13xx protocol returned an error
     #[from]
     Protocol(bm13xx_protocol::Error),
+    /// The BM13xx ASIC returned an error
+    #[from]
+    Asic(bm13xx_asic::Error),
     /// The serial interface returned an error
     Io(IO),
     /// The gpio interface returned an error on Busy signal
@@ -34,12 +37,56 @@ impl<IO: core::fmt::Debug, B: core::fmt::Debug, R: core::fmt::Debug> core::error
 {
 }
 
-#[rustversion::since(1.81)]
+#[cfg(feature = "std")]
+#[rustversion::before(1.81)]
+impl<IO: core::fmt::Debug, B: core::fmt::Debug, R: core::fmt::Debug> std::error::Error
+    for Error<IO, B, R>
+{
+}
+
+/// ### Example
+/// ```
+/// use bm13xx_chain::Error;
+///
+/// type ChainError = Error<&'static str, &'static str, &'static str>;
+///
+/// let messages = [
+///     (ChainError::EmptyChain, "no ASIC enumerated on the chain"),
+///     (
+///         ChainError::Protocol(bm13xx_protocol::Error::InvalidPreamble),
+///         "protocol error: invalid response preamble",
+///     ),
+///     (
+///         ChainError::Asic(bm13xx_asic::Error::UnknownRegister { reg_addr: 0xfc }),
+///         "ASIC error: unknown register 0xfc",
+///     ),
+///     (ChainError::Io("timeout"), "serial interface error: \"timeout\""),
+///     (ChainError::Busy("gpio"), "busy signal error: \"gpio\""),
+///     (ChainError::Reset("gpio"), "reset signal error: \"gpio\""),
+///     (ChainError::SetBaudrate, "failed to set the serial interface baudrate"),
+/// ];
+/// for (err, msg) in messages {
+///     assert_eq!(err.to_string(), msg);
+/// }
+/// ```
 impl<IO: core::fmt::Debug, B: core::fmt::Debug, R: core::fmt::Debug> core::fmt::Display
     for Error<IO, B, R>
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Error::UnexpectedResponse { resp } => write!(f, "unexpected response {resp:x?}"),
+            Error::BadRegisterResponse { reg_resp } => {
+                write!(f, "bad register response {reg_resp:x?}")
+            }
+            Error::UnexpectedAsic { chip_ident } => write!(f, "unexpected ASIC {chip_ident:x?}"),
+            Error::EmptyChain => write!(f, "no ASIC enumerated on the chain"),
+            Error::Protocol(protocol_err) => write!(f, "protocol error: {protocol_err}"),
+            Error::Asic(asic_err) => write!(f, "ASIC error: {asic_err}"),
+            Error::Io(io_err) => write!(f, "serial interface error: {io_err:?}"),
+            Error::Busy(gpio_err) => write!(f, "busy signal error: {gpio_err:?}"),
+            Error::Reset(gpio_err) => write!(f, "reset signal error: {gpio_err:?}"),
+            Error::SetBaudrate => write!(f, "failed to set the serial interface baudrate"),
+        }
     }
 }
 
@@ -62,6 +109,7 @@ impl<IO: core::fmt::Debug, B: core::fmt::Debug, R: core::fmt::Debug> core::fmt::
                 .finish(),
             Error::EmptyChain => f.debug_struct("EmptyChain").finish(),
             Error::Protocol(protocol_err) => f.debug_tuple("Protocol").field(protocol_err).finish(),
+            Error::Asic(asic_err) => f.debug_tuple("Asic").field(asic_err).finish(),
             Error::Io(io_err) => f.debug_tuple("Io").field(io_err).finish(),
             Error::Busy(gpio_err) => f.debug_tuple("Busy").field(gpio_err).finish(),
             Error::Reset(gpio_err) => f.debug_tuple("Reset").field(gpio_err).finish(),
@@ -65,6 +65,10 @@
 
 #![no_std]
 #![macro_use]
+
+#[cfg(feature = "std")]
+extern crate std;
+
 pub(crate) mod fmt;
 
 mod error;
@@ -73,7 +77,10 @@ use core::time::Duration;
 
 pub use self::error::{Error, Result};
 
-use bm13xx_asic::{register::ChipIdentification, Asic, CmdDelay};
+use bm13xx_asic::{
+    check_addr_interval, check_chain_geometry, check_version_mask, chip_addr_checked, nonce,
+    register::ChipIdentification, Asic, CmdDelay,
+};
 use bm13xx_protocol::{
     command::{Command, Destination},
     response::{Response, ResponseType, FRAME_SIZE, FRAME_SIZE_VER},
@@ -92,7 +99,6 @@ pub trait Baud {
 const RX_BUF_SIZE: usize = 256;
 
 const NONCE_BITS: u32 = u32::BITS;
-const CHIP_ADDR_BITS: u32 = u8::BITS;
 
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -116,7 +122,7 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
     Chain<A, U, OB, OR, D>
 {
     async fn send(&mut self, step: CmdDelay) -> Result<(), U::Error, OB::Error, OR::Error> {
-        self.uart.write_all(&step.cmd).await.map_err(Error::Io)?;
+        self.uart.write_all(step.bytes()).await.map_err(Error::Io)?;
         self.delay.delay_ms(step.delay_ms).await;
         Ok(())
     }
@@ -125,7 +131,11 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
     ///
     /// Total time to roll the Nonce space and Version space (if HW version rolling is enabled) for the full chain at current Hash frequency.
     /// A new job should be sent every `rolling_duration`.
+    /// Returns `Duration::ZERO` if the Hash frequency is null.
     pub fn rolling_duration(&self) -> Duration {
+        if self.asic.hash_freq().raw() == 0 {
+            return Duration::ZERO;
+        }
         let space = self.chip_nonce_space as f32
             * if let Some(mask) = self.version_rolling_mask {
                 (mask.count_ones() - 1) as f32
@@ -135,10 +145,72 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
         Duration::from_secs_f32(space / (self.asic.hash_freq().raw() as f32) / 1_000.0)
     }
 
+    /// ## Get the Nonce search rate
+    ///
+    /// Number of nonces searched per second by `chip_count` chips of the chain, derived from the
+    /// Nonce space size and `rolling_duration`.
+    /// Unlike `theoretical_hashrate_ghs`, which counts SHA operations, this measures the Nonce space coverage.
+    pub fn nonce_search_rate(&self, chip_count: usize) -> f32 {
+        nonce::nonce_search_rate(
+            self.chip_nonce_space,
+            self.version_rolling_mask,
+            self.rolling_duration(),
+            chip_count,
+        )
+    }
+
+    /// ## Get the Nonce search rate of a Small Core
+    ///
+    /// Number of nonces searched per second by each Small Core at current Hash frequency,
+    /// see `nonce::small_core_nonce_rate`.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::small_core_nonce_rate;
+    /// use fugit::HertzU64;
+    ///
+    /// // BM1366 chain at 500 MHz: 894 Small Cores sharing the full Nonce space
+    /// assert_eq!(small_core_nonce_rate(HertzU64::MHz(500), 1 << 32, None, 894), 500_000_000.0);
+    /// // same with the 0x1fffe000 Version mask: 2^16 Version combinations per Nonce
+    /// assert_eq!(small_core_nonce_rate(HertzU64::MHz(500), 1 << 32, Some(0x1fff_e000), 894), 7_629.394_5);
+    /// ```
+    pub fn small_core_nonce_rate(&self) -> f32 {
+        nonce::small_core_nonce_rate(
+            self.asic.hash_freq(),
+            self.chip_nonce_space,
+            self.version_rolling_mask,
+            self.asic.small_core_count(),
+        )
+    }
+
+    /// ## Get the number of bits rolled per chip
+    ///
+    /// Base-2 logarithm of the space rolled by each chip every `rolling_duration`,
+    /// see `nonce::rolled_bits`.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::nonce::rolled_bits;
+    ///
+    /// // chips rolling 2^14 nonces each, without HW version rolling
+    /// assert_eq!(rolled_bits(1 << 14, None), 14);
+    /// // same with the 0x1fffe000 Version mask: 16 more bits
+    /// assert_eq!(rolled_bits(1 << 14, Some(0x1fff_e000)), 30);
+    /// ```
+    pub fn rolled_bits(&self) -> usize {
+        nonce::rolled_bits(self.chip_nonce_space, self.version_rolling_mask)
+    }
+
+    /// ## Get the fraction of the Nonce space covered by the chain
+    ///
+    /// See `nonce::nonce_space_coverage`, 1.0 means no Nonce is lost to the Chip Nonce Offset rounding.
+    pub fn nonce_space_coverage(&self) -> f32 {
+        nonce::nonce_space_coverage(self.asic.cno_bits(), self.asic_cnt)
+    }
+
     /// ## Get the theoretical Hashrate in GH/s
     pub fn theoretical_hashrate_ghs(&self) -> f32 {
-        (self.asic.hash_freq().raw() as f32 * self.asic.small_core_count() as f32 / 1_000_000_000.0)
-            * self.asic_cnt as f32
+        self.asic.theoretical_hashrate_ghs() * self.asic_cnt as f32
     }
 
     /// ## Poll for a response
@@ -211,6 +283,14 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
                     );
                     expected_frame_size
                 }
+                // command decoding errors, never returned by the response parsers
+                Err(
+                    e @ (bm13xx_protocol::Error::InvalidLength { .. }
+                    | bm13xx_protocol::Error::UnknownCommand { .. }),
+                ) => {
+                    error!("Ignoring Frame {:x?}: {:?}", frame, e);
+                    expected_frame_size
+                }
             };
             if self.rx_free_pos > used {
                 debug!("copy reminder {} bytes @0", self.rx_free_pos - used);
@@ -297,10 +377,11 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
         chain.asic_addr_interval = 256 / asic_cnt;
         chain.asic_cnt = asic_cnt;
         chain.chip_nonce_space = chain.asic_addr_interval
-            << (NONCE_BITS
-                - (chain.asic.core_count().ilog2() + 1)
-                - (chain.asic.core_small_core_count().ilog2() + 1)
-                - CHIP_ADDR_BITS);
+            << nonce::nonce_chip_addr_shift(
+                chain.asic.core_count().ilog2() + 1,
+                chain.asic.core_small_core_count().ilog2() + 1,
+            )
+            .expect("Nonce fields do not fit in 32 bits");
         // TODO: try to determine domain_cnt according to known topologies
         chain.delay.delay_ms(50).await;
         if post_s19jpro {
@@ -319,7 +400,8 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
         }
         chain.delay.delay_ms(30).await;
         for i in 0..asic_cnt {
-            let cmd = Command::set_chip_addr((i * chain.asic_addr_interval) as u8);
+            let chip_addr = chip_addr_checked(i, chain.asic_addr_interval, asic_cnt)?;
+            let cmd = Command::set_chip_addr(chip_addr);
             chain.uart.write_all(&cmd).await.map_err(Error::Io)?;
             chain.delay.delay_ms(10).await;
         }
@@ -327,6 +409,22 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
         Ok(chain)
     }
 
+    /// ## Set the ASIC address interval
+    ///
+    /// Overwrite the `asic_addr_interval` set during enumeration, typically to match a known board.
+    ///
+    /// ### Errors
+    ///
+    /// - Asic error if some chip addresses would collide
+    pub fn set_asic_addr_interval(
+        &mut self,
+        asic_addr_interval: usize,
+    ) -> Result<(), U::Error, OB::Error, OR::Error> {
+        check_addr_interval(self.asic_cnt, asic_addr_interval)?;
+        self.asic_addr_interval = asic_addr_interval;
+        Ok(())
+    }
+
     /// ## Set the number of domains in the chain
     ///
     /// In case we enumarted an unknown topology (custom HB?), this function is mandatory to set the number of domains.
@@ -356,14 +454,20 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
     }
 
     /// ## Change the baudrate used by the chain to communicate
+    ///
+    /// ### Errors
+    ///
+    /// - Asic error if the chain geometry does not give valid chip addresses
     pub async fn change_baudrate(
         &mut self,
         baudrate: u32,
     ) -> Result<(), U::Error, OB::Error, OR::Error> {
+        let domain_asic_cnt = self.asic_cnt.checked_div(self.domain_cnt).unwrap_or(0);
+        check_chain_geometry(self.domain_cnt, domain_asic_cnt, self.asic_addr_interval)?;
         while let Some(step) = self.asic.set_baudrate_next(
             baudrate,
             self.domain_cnt,
-            self.asic_cnt / self.domain_cnt,
+            domain_asic_cnt,
             self.asic_addr_interval,
         ) {
             self.send(step).await?;
@@ -377,10 +481,8 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
     /// ## Reset all cores of all chip in the chain
     pub async fn reset_all_cores(&mut self) -> Result<(), U::Error, OB::Error, OR::Error> {
         for asic_i in 0..self.asic_cnt {
-            while let Some(step) = self
-                .asic
-                .reset_core_next(Destination::Chip((asic_i * self.asic_addr_interval) as u8))
-            {
+            let chip_addr = chip_addr_checked(asic_i, self.asic_addr_interval, self.asic_cnt)?;
+            while let Some(step) = self.asic.reset_core_next(Destination::Chip(chip_addr)) {
                 self.send(step).await?;
             }
         }
@@ -406,6 +508,7 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
     pub async fn split_nonce_between_chips(
         &mut self,
     ) -> Result<(), U::Error, OB::Error, OR::Error> {
+        check_addr_interval(self.asic_cnt, self.asic_addr_interval)?;
         while let Some(step) = self
             .asic
             .split_nonce_between_chips_next(self.asic_cnt, self.asic_addr_interval)
@@ -427,10 +530,15 @@ impl<A: Asic, U: Read + ReadReady + Write + Baud, OB: OutputPin, OR: OutputPin,
     /// ## Enable Version Rolling in chips
     ///
     /// Enable Hardware Version Rolling with the given version mask.
+    ///
+    /// ### Errors
+    ///
+    /// - Asic error if the mask is wider than the 16 bits supported by the chips, see `check_version_mask()`
     pub async fn enable_version_rolling(
         &mut self,
         mask: u32,
     ) -> Result<(), U::Error, OB::Error, OR::Error> {
+        check_version_mask(mask)?;
         if self.version_rolling_mask.is_none() {
             while let Some(step) = self.asic.set_version_rolling_next(mask) {
                 self.send(step).await?;
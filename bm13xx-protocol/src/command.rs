@@ -3,9 +3,24 @@
 use heapless::Vec;
 
 use crate::crc::{crc16, crc5};
+use crate::{Error, Result};
 
 /// Some command can be send to All chip in the chain or to a specific one
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// ## Example
+/// ```
+/// use bm13xx_protocol::command::Destination;
+/// use heapless::FnvIndexMap;
+///
+/// let mut shares = FnvIndexMap::<Destination, u32, 4>::new();
+/// shares.insert(Destination::All, 3).unwrap();
+/// shares.insert(Destination::Chip(2), 1).unwrap();
+/// *shares.get_mut(&Destination::Chip(2)).unwrap() += 1;
+/// assert_eq!(shares.get(&Destination::All), Some(&3));
+/// assert_eq!(shares.get(&Destination::Chip(2)), Some(&2));
+/// assert_eq!(shares.get(&Destination::Chip(4)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum Destination {
     All,
@@ -16,13 +31,41 @@ pub enum Destination {
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct Command;
 
+/// A decoded command frame, see `Command::decode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum DecodedCommand {
+    ChainInactive,
+    SetChipAddr {
+        chip_addr: u8,
+    },
+    ReadReg {
+        reg_addr: u8,
+        dest: Destination,
+    },
+    WriteReg {
+        reg_addr: u8,
+        reg_val: u32,
+        dest: Destination,
+    },
+}
+
 impl Command {
-    const CMD_ALL_CHIP: u8 = 0x10;
-    const CMD_SEND_JOB: u8 = 0x21;
-    const CMD_SET_CHIP_ADDR: u8 = 0x40;
-    const CMD_WRITE_REGISTER: u8 = 0x41;
-    const CMD_READ_REGISTER: u8 = 0x42;
-    const CMD_CHAIN_INACTIVE: u8 = 0x43;
+    /// Preamble of all the command frames
+    pub const PREAMBLE: [u8; 2] = [0x55, 0xAA];
+
+    /// Flag added to an opcode to send the command to all chips
+    pub const CMD_ALL_CHIP: u8 = 0x10;
+    /// Send Job opcode
+    pub const CMD_SEND_JOB: u8 = 0x21;
+    /// Set Chip Address opcode
+    pub const CMD_SET_CHIP_ADDR: u8 = 0x40;
+    /// Write Register opcode
+    pub const CMD_WRITE_REGISTER: u8 = 0x41;
+    /// Read Register opcode
+    pub const CMD_READ_REGISTER: u8 = 0x42;
+    /// Chain Inactive opcode
+    pub const CMD_CHAIN_INACTIVE: u8 = 0x43;
 
     /// # Chain Inactive Command
     ///
@@ -38,8 +81,8 @@ impl Command {
     /// ```
     pub fn chain_inactive() -> [u8; 7] {
         let mut data: [u8; 7] = [
-            0x55,
-            0xAA,
+            Self::PREAMBLE[0],
+            Self::PREAMBLE[1],
             Self::CMD_CHAIN_INACTIVE + Self::CMD_ALL_CHIP,
             5,
             0,
@@ -74,7 +117,15 @@ impl Command {
     /// assert_eq!(cmd, [0x55, 0xAA, 0x40, 0x05, 0x08, 0x00, 0x07]);
     /// ```
     pub fn set_chip_addr(addr: u8) -> [u8; 7] {
-        let mut data: [u8; 7] = [0x55, 0xAA, Self::CMD_SET_CHIP_ADDR, 5, addr, 0, 0];
+        let mut data: [u8; 7] = [
+            Self::PREAMBLE[0],
+            Self::PREAMBLE[1],
+            Self::CMD_SET_CHIP_ADDR,
+            5,
+            addr,
+            0,
+            0,
+        ];
         data[6] = crc5(&data[2..6]);
         data
     }
@@ -103,7 +154,15 @@ impl Command {
     /// assert_eq!(cmd, [0x55, 0xAA, 0x42, 0x05, 0x40, 0x1C, 0x0B]);
     /// ```
     pub fn read_reg(reg_addr: u8, dest: Destination) -> [u8; 7] {
-        let mut data: [u8; 7] = [0x55, 0xAA, Self::CMD_READ_REGISTER, 5, 0, reg_addr, 0];
+        let mut data: [u8; 7] = [
+            Self::PREAMBLE[0],
+            Self::PREAMBLE[1],
+            Self::CMD_READ_REGISTER,
+            5,
+            0,
+            reg_addr,
+            0,
+        ];
         match dest {
             Destination::All => data[2] += Self::CMD_ALL_CHIP,
             Destination::Chip(c) => data[4] = c,
@@ -133,8 +192,8 @@ impl Command {
     /// ```
     pub fn write_reg(reg_addr: u8, reg_val: u32, dest: Destination) -> [u8; 11] {
         let mut data: [u8; 11] = [
-            0x55,
-            0xAA,
+            Self::PREAMBLE[0],
+            Self::PREAMBLE[1],
             Self::CMD_WRITE_REGISTER,
             9,
             0,
@@ -153,6 +212,109 @@ impl Command {
         data
     }
 
+    /// # Decode a Command
+    ///
+    /// Decode a Chain Inactive, Set Chip Address, Read Register or Write Register command frame,
+    /// like the ones found in captured init sequences.
+    ///
+    /// ## Errors
+    ///
+    /// - `Err(Error::InvalidPreamble)` if it first 2 bytes are not `[0x55, 0xAA]`.
+    /// - `Err(Error::InvalidLength)` if the frame length does not match the command.
+    /// - `Err(Error::UnknownCommand)` if the command is not one of the above (like Jobs).
+    /// - `Err(Error::InvalidCrc)` if the CRC5 is not valid.
+    ///
+    /// ## Example
+    /// ```
+    /// use bm13xx_protocol::command::{Command, DecodedCommand, Destination};
+    /// use bm13xx_protocol::Error;
+    ///
+    /// // BM1370 Init sequence
+    /// let init = [
+    ///     [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8B, 0x00, 0x12],
+    ///     [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x10, 0x12],
+    ///     [0x55, 0xaa, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08],
+    ///     [0x55, 0xaa, 0x51, 0x09, 0x00, 0x54, 0x00, 0x00, 0x00, 0x03, 0x1d],
+    /// ];
+    /// let regs = [(0x3c, 0x8000_8B00), (0x3c, 0x8000_8010), (0x14, 0x0000_00FF), (0x54, 0x0000_0003)];
+    /// for (frame, (reg_addr, reg_val)) in init.iter().zip(regs) {
+    ///     assert_eq!(
+    ///         Command::decode(frame),
+    ///         Ok(DecodedCommand::WriteReg { reg_addr, reg_val, dest: Destination::All })
+    ///     );
+    /// }
+    ///
+    /// assert_eq!(
+    ///     Command::decode(&Command::read_reg(0x1C, Destination::Chip(64))),
+    ///     Ok(DecodedCommand::ReadReg { reg_addr: 0x1C, dest: Destination::Chip(64) })
+    /// );
+    /// assert_eq!(Command::decode(&Command::chain_inactive()), Ok(DecodedCommand::ChainInactive));
+    /// assert_eq!(
+    ///     Command::decode(&Command::set_chip_addr(0x08)),
+    ///     Ok(DecodedCommand::SetChipAddr { chip_addr: 0x08 })
+    /// );
+    /// assert_eq!(
+    ///     Command::decode(&[0x55, 0xaa, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x00]),
+    ///     Err(Error::InvalidCrc { expected: 0x08, actual: 0x00 })
+    /// );
+    /// assert_eq!(
+    ///     Command::decode(&[0x55, 0xaa, 0x51, 0x09, 0x00, 0x14]),
+    ///     Err(Error::InvalidLength { expected: 11, actual: 6 })
+    /// );
+    /// assert_eq!(Command::decode(&[0xAA, 0x55, 0x53, 0x05, 0x00, 0x00, 0x03]), Err(Error::InvalidPreamble));
+    /// assert_eq!(Command::decode(&[0x55, 0xAA, 0x21, 0x05]), Err(Error::UnknownCommand { cmd: 0x21 }));
+    /// ```
+    pub fn decode(frame: &[u8]) -> Result<DecodedCommand> {
+        if frame.len() < 4 {
+            return Err(Error::InvalidLength {
+                expected: 4,
+                actual: frame.len(),
+            });
+        }
+        if frame[0..2] != Self::PREAMBLE {
+            return Err(Error::InvalidPreamble);
+        }
+        let cmd = frame[2] & !Self::CMD_ALL_CHIP;
+        let expected = match cmd {
+            Self::CMD_WRITE_REGISTER => 11,
+            Self::CMD_SET_CHIP_ADDR | Self::CMD_READ_REGISTER | Self::CMD_CHAIN_INACTIVE => 7,
+            _ => return Err(Error::UnknownCommand { cmd: frame[2] }),
+        };
+        if frame.len() != expected || frame[3] as usize != expected - 2 {
+            return Err(Error::InvalidLength {
+                expected,
+                actual: frame.len(),
+            });
+        }
+        let crc = crc5(&frame[2..expected - 1]);
+        if crc != frame[expected - 1] {
+            return Err(Error::InvalidCrc {
+                expected: crc,
+                actual: frame[expected - 1],
+            });
+        }
+        let dest = if frame[2] & Self::CMD_ALL_CHIP == Self::CMD_ALL_CHIP {
+            Destination::All
+        } else {
+            Destination::Chip(frame[4])
+        };
+        Ok(match cmd {
+            Self::CMD_WRITE_REGISTER => DecodedCommand::WriteReg {
+                reg_addr: frame[5],
+                reg_val: u32::from_be_bytes([frame[6], frame[7], frame[8], frame[9]]),
+                dest,
+            },
+            Self::CMD_READ_REGISTER => DecodedCommand::ReadReg {
+                reg_addr: frame[5],
+                dest,
+            },
+            Self::CMD_SET_CHIP_ADDR => DecodedCommand::SetChipAddr {
+                chip_addr: frame[4],
+            },
+            _ => DecodedCommand::ChainInactive,
+        })
+    }
+
     /// # Job with Midstates Command
     ///
     /// ## Example
@@ -217,8 +379,7 @@ impl Command {
         core_small_core_cnt: usize,
     ) -> Vec<u8, 152> {
         let mut data = Vec::new();
-        data.push(0x55).unwrap();
-        data.push(0xAA).unwrap();
+        data.extend_from_slice(&Self::PREAMBLE).unwrap();
         data.push(Self::CMD_SEND_JOB).unwrap();
         data.push(22 + (midstates.len() * 32) as u8).unwrap();
         data.push(job_id << if core_small_core_cnt > 5 { 3 } else { 2 })
@@ -273,8 +434,7 @@ impl Command {
         version: u32,
     ) -> [u8; 88] {
         let mut data = [0; 88];
-        data[0] = 0x55;
-        data[1] = 0xAA;
+        data[0..2].clone_from_slice(&Self::PREAMBLE);
         data[2] = Self::CMD_SEND_JOB;
         // data[3] = 54;
         data[3] = data.len() as u8 - 32 - 2;
@@ -300,3 +460,78 @@ impl Command {
         data
     }
 }
+
+/// # Command Encoder
+///
+/// Build the same command frames as `Command`, but with a custom preamble,
+/// for non-standard but compatible links. The CRC does not cover the preamble,
+/// so only the 2 leading bytes differ from the `Command` frames.
+///
+/// ## Example
+/// ```
+/// use bm13xx_protocol::command::{Command, CommandEncoder, Destination};
+///
+/// let enc = CommandEncoder::default();
+/// assert_eq!(
+///     enc.write_reg(0x18, 0x0000_7A31, Destination::Chip(64)),
+///     Command::write_reg(0x18, 0x0000_7A31, Destination::Chip(64))
+/// );
+///
+/// let enc = CommandEncoder::new([0xA5, 0x5A]);
+/// let cmd = enc.write_reg(0x18, 0x0000_7A31, Destination::Chip(64));
+/// assert_eq!(cmd, [0xA5, 0x5A, 0x41, 0x09, 0x40, 0x18, 0x00, 0x00, 0x7A, 0x31, 0x11]);
+/// assert_eq!(cmd[2..], Command::write_reg(0x18, 0x0000_7A31, Destination::Chip(64))[2..]);
+/// assert_eq!(enc.read_reg(0x00, Destination::All), [0xA5, 0x5A, 0x52, 0x05, 0x00, 0x00, 0x0A]);
+/// assert_eq!(cmd[2], Command::CMD_WRITE_REGISTER);
+/// assert_eq!(enc.chain_inactive()[2], Command::CMD_CHAIN_INACTIVE | Command::CMD_ALL_CHIP);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct CommandEncoder {
+    pub preamble: [u8; 2],
+}
+
+impl Default for CommandEncoder {
+    fn default() -> Self {
+        Self::new(Command::PREAMBLE)
+    }
+}
+
+impl CommandEncoder {
+    pub const fn new(preamble: [u8; 2]) -> Self {
+        Self { preamble }
+    }
+
+    fn with_preamble<const N: usize>(&self, mut data: [u8; N]) -> [u8; N] {
+        data[0..2].clone_from_slice(&self.preamble);
+        data
+    }
+
+    /// # Chain Inactive Command
+    ///
+    /// See `Command::chain_inactive`.
+    pub fn chain_inactive(&self) -> [u8; 7] {
+        self.with_preamble(Command::chain_inactive())
+    }
+
+    /// # Set Chip Address Command
+    ///
+    /// See `Command::set_chip_addr`.
+    pub fn set_chip_addr(&self, addr: u8) -> [u8; 7] {
+        self.with_preamble(Command::set_chip_addr(addr))
+    }
+
+    /// # Read Register Command
+    ///
+    /// See `Command::read_reg`.
+    pub fn read_reg(&self, reg_addr: u8, dest: Destination) -> [u8; 7] {
+        self.with_preamble(Command::read_reg(reg_addr, dest))
+    }
+
+    /// # Write Register Command
+    ///
+    /// See `Command::write_reg`.
+    pub fn write_reg(&self, reg_addr: u8, reg_val: u32, dest: Destination) -> [u8; 11] {
+        self.with_preamble(Command::write_reg(reg_addr, reg_val, dest))
+    }
+}
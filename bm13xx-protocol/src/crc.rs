@@ -22,6 +22,18 @@ const CRC16: Crc<u16> = Crc::<u16>::new(&Algorithm {
     residue: 0x0000,
 });
 
+/// ## Compute the CRC5 of a payload
+///
+/// Used by the command frames, computed over the frame without the preamble.
+///
+/// ### Example
+/// ```
+/// use bm13xx_protocol::crc5;
+///
+/// // BM1370 init, write ClockDelayCtrl core register
+/// let frame = [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8b, 0x00, 0x12];
+/// assert_eq!(crc5(&frame[2..10]), frame[10]);
+/// ```
 pub const fn crc5(data: &[u8]) -> u8 {
     CRC5.checksum(data)
 }
@@ -105,6 +117,28 @@ pub const fn crc5_bits(data: &[u8]) -> u8 {
     crc5
 }
 
+/// ## Compute the CRC16 of a payload
+///
+/// Used by the work (job) frames: CRC-16/CCITT-FALSE (polynomial 0x1021, init 0xffff),
+/// computed over the frame without the preamble and appended big endian.
+///
+/// ### Example
+/// ```
+/// use bm13xx_protocol::crc16;
+///
+/// // captured job header frame, see `Command::job_header()`
+/// let frame = [
+///     0x55, 0xaa, 0x21, 0x36, 0xa8, 0x01, 0x00, 0x00, 0x00, 0x00, 0x50, 0x24, 0x04, 0x17,
+///     0x83, 0xde, 0x70, 0x65, 0x2d, 0x19, 0x75, 0x74, 0x66, 0x63, 0x21, 0x46, 0xb8, 0x71,
+///     0x7a, 0x7e, 0xfe, 0x83, 0xec, 0x35, 0xc0, 0x96, 0xf3, 0xa4, 0xc0, 0xd8, 0x86, 0xda,
+///     0xa8, 0x0e, 0x70, 0x2e, 0xed, 0xe9, 0x96, 0x71, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+///     0x00, 0x00, 0xff, 0x86, 0x02, 0x00, 0x5b, 0xa4, 0xa5, 0x0e, 0x55, 0xd3, 0x00, 0xfc,
+///     0xae, 0x0e, 0xd5, 0x56, 0xd7, 0x76, 0xd8, 0x1a, 0x38, 0xe1, 0x99, 0x1f, 0x00, 0x00,
+///     0x00, 0x20, 0x30, 0xb9,
+/// ];
+/// assert_eq!(crc16(&frame[2..86]), 0x30b9);
+/// assert_eq!(crc16(&frame[2..]), 0); // full frame check
+/// ```
 pub const fn crc16(data: &[u8]) -> u16 {
     CRC16.checksum(data)
 }
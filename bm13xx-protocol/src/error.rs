@@ -8,15 +8,69 @@ pub enum Error {
     // -- response
     InvalidPreamble,
     UnsupportedCoreSmallCoreCnt,
-    InvalidCrc { expected: u8, actual: u8 },
+    InvalidCrc {
+        expected: u8,
+        actual: u8,
+    },
+    // -- command
+    #[from(ignore)]
+    InvalidLength {
+        expected: usize,
+        actual: usize,
+    },
+    #[from(ignore)]
+    UnknownCommand {
+        cmd: u8,
+    },
 }
 
 #[rustversion::since(1.81)]
 impl core::error::Error for Error {}
 
-#[rustversion::since(1.81)]
+#[cfg(feature = "std")]
+#[rustversion::before(1.81)]
+impl std::error::Error for Error {}
+
+/// ### Example
+/// ```
+/// use bm13xx_protocol::Error;
+///
+/// assert_eq!(Error::InvalidPreamble.to_string(), "invalid response preamble");
+/// assert_eq!(
+///     Error::UnsupportedCoreSmallCoreCnt.to_string(),
+///     "unsupported core small core count"
+/// );
+/// assert_eq!(
+///     Error::InvalidCrc { expected: 0x1c, actual: 0x03 }.to_string(),
+///     "invalid CRC: expected 0x1c, got 0x03"
+/// );
+/// assert_eq!(
+///     Error::InvalidLength { expected: 9, actual: 7 }.to_string(),
+///     "invalid frame length: expected 9 bytes, got 7"
+/// );
+/// assert_eq!(
+///     Error::UnknownCommand { cmd: 0x42 }.to_string(),
+///     "unknown command 0x42"
+/// );
+/// ```
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Error::InvalidPreamble => write!(f, "invalid response preamble"),
+            Error::UnsupportedCoreSmallCoreCnt => write!(f, "unsupported core small core count"),
+            Error::InvalidCrc { expected, actual } => {
+                write!(
+                    f,
+                    "invalid CRC: expected {expected:#04x}, got {actual:#04x}"
+                )
+            }
+            Error::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "invalid frame length: expected {expected} bytes, got {actual}"
+                )
+            }
+            Error::UnknownCommand { cmd } => write!(f, "unknown command {cmd:#04x}"),
+        }
     }
 }
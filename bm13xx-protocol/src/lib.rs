@@ -2,6 +2,10 @@
 
 #![no_std]
 #![macro_use]
+
+#[cfg(feature = "std")]
+extern crate std;
+
 pub(crate) mod fmt;
 
 mod crc;
@@ -10,4 +14,5 @@ mod error;
 pub mod command;
 pub mod response;
 
+pub use self::crc::{crc16, crc5};
 pub use self::error::{Error, Result};
@@ -42,6 +42,24 @@ pub enum ResponseType {
 pub const FRAME_SIZE: usize = 9;
 pub const FRAME_SIZE_VER: usize = 11;
 
+/// # Nonce from Frame Bytes
+///
+/// Get the nonce from the 4 nonce bytes of a Job response frame, as they are received on RO.
+/// The chip sends the nonce least significant byte first (little-endian), the resulting `u32`
+/// is in host order and can be given to the nonce decode helpers.
+///
+/// ## Example
+///
+/// ```
+/// use bm13xx_protocol::response::nonce_from_frame_bytes;
+///
+/// // first Bitaxe Block 853742
+/// assert_eq!(nonce_from_frame_bytes(&[0xc8, 0x32, 0x67, 0x90]), 0x906732c8);
+/// ```
+pub const fn nonce_from_frame_bytes(bytes: &[u8; 4]) -> u32 {
+    u32::from_le_bytes(*bytes)
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct Response;
@@ -130,7 +148,7 @@ impl Response {
             let small_core_mask = core_small_core_cnt - 1;
             let small_core_bits = small_core_mask.count_ones();
             return Ok(ResponseType::Job(JobResponse {
-                nonce: u32::from_le_bytes(data[2..6].try_into().unwrap()),
+                nonce: nonce_from_frame_bytes(data[2..6].try_into().unwrap()),
                 midstate_id: data[6] as usize,
                 job_id: ((data[7] as usize) >> small_core_bits) & 0b1_1111,
                 small_core_id: (data[7] as usize) & small_core_mask,
@@ -244,11 +262,11 @@ impl Response {
             let small_core_mask = core_small_core_cnt - 1;
             let small_core_bits = small_core_mask.count_ones();
             let chunk = ((data[6] as u16) << 8) | data[7] as u16;
-            let nonce = u32::from_le_bytes(data[2..6].try_into().unwrap());
+            let nonce = nonce_from_frame_bytes(data[2..6].try_into().unwrap());
             // TODO: https://github.com/GPTechinno/bm13xx-rs/blob/4973f1bea844823c9f00f9083ea25487e5151506/bm1370/src/lib.rs#L145-L151
             let chip_addr = (((nonce >> (32 - 7 - 16)) & 0b1111_1111_1111_1111) as usize
                 * chain_asic_num)
-            >> 16;
+                >> 16;
             return Ok(ResponseType::JobVer(JobVersionResponse {
                 nonce,
                 unknown: (chunk >> (small_core_bits + 5)) as u8,